@@ -0,0 +1,318 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rust_quiz::gate::PassMark;
+use rust_quiz::loader::Format;
+use rust_quiz::quiz::Level;
+use rust_quiz::schedule::NEW_PER_DAY;
+use rust_quiz::scoring::Scoring;
+use rust_quiz::select::Query;
+
+/// Where `review` keeps its schedule unless `--state` says otherwise.
+pub const REVIEW_STATE: &str = "rust_quiz_review.json";
+/// Where finished quizzes are logged for `stats` unless `--history` says
+/// otherwise.
+pub const HISTORY_FILE: &str = "rust_quiz_history.json";
+
+/// Rust quizzes in the terminal. Without a command, plays a quiz.
+#[derive(Debug, Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub play: PlayArgs,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Play a quiz (the default)
+    Play(PlayArgs),
+    /// Practice the questions due today, spaced out by how well you know them
+    Review(ReviewArgs),
+    /// Show your accuracy per topic and level
+    Stats(StatsArgs),
+    /// Check the questions for mistakes
+    Lint(PackArgs),
+    /// Write questions out as a question pack
+    Export(ExportArgs),
+    /// List the questions
+    List(ListArgs),
+    /// Show one question with its answer
+    Show(ShowArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct PackArgs {
+    /// Extra question packs (.toml or .json) used alongside the built-in questions
+    #[arg(value_name = "PACK")]
+    pub packs: Vec<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct HistoryArgs {
+    /// File finished quizzes are logged to, for `stats`
+    #[arg(long = "history", value_name = "FILE", default_value = HISTORY_FILE)]
+    pub path: PathBuf,
+}
+
+/// How the quiz talks to whoever is answering.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Protocol {
+    /// Prompts and feedback for a person
+    #[default]
+    Plain,
+    /// One JSON event per line on standard output, one JSON command per line on standard input
+    Jsonl,
+}
+
+#[derive(Debug, Args)]
+pub struct InterfaceArgs {
+    /// Use the full-screen interface instead of line-by-line prompts
+    #[arg(long)]
+    pub tui: bool,
+    /// `jsonl` lets another program drive the quiz
+    #[arg(long, value_enum, value_name = "PROTOCOL", default_value_t, conflicts_with = "tui")]
+    pub protocol: Protocol,
+}
+
+/// Which questions to use.
+#[derive(Debug, Args)]
+pub struct FilterArgs {
+    /// Only questions with this tag; repeat for any of several
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+    /// Leave out questions with this tag
+    #[arg(long = "exclude-tag", value_name = "TAG")]
+    pub exclude_tags: Vec<String>,
+    /// A level, or a range such as intro..beginner+
+    #[arg(long, value_name = "LEVEL[..LEVEL]", value_parser = parse_levels)]
+    pub level: Option<RangeInclusive<Level>>,
+    /// Only these question ids, comma-separated
+    #[arg(long, value_name = "IDS", value_delimiter = ',')]
+    pub ids: Vec<u32>,
+    /// How many questions to use, picked at random
+    #[arg(long, value_name = "N", value_parser = positive)]
+    pub count: Option<usize>,
+}
+
+impl FilterArgs {
+    pub fn query(&self) -> Query {
+        let mut query = Query::new().ids(self.ids.clone());
+        for tag in &self.tags {
+            query = query.tag(tag.as_str());
+        }
+        for tag in &self.exclude_tags {
+            query = query.exclude_tag(tag.as_str());
+        }
+        if let Some(levels) = &self.level {
+            query = query.levels(levels.clone());
+        }
+        if let Some(count) = self.count {
+            query = query.count(count);
+        }
+        query
+    }
+
+    /// [`Self::query`] with a fresh seed when `--count` is given, so the
+    /// questions are picked at random rather than taken from the top.
+    pub fn random_query(&self) -> Query {
+        match self.count {
+            Some(_) => self.query().seed(rand::random()),
+            None => self.query(),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct PlayArgs {
+    #[command(flatten)]
+    pub filters: FilterArgs,
+    /// Play every matching question in order, without the menu
+    #[arg(long, conflicts_with_all = ["count", "adaptive"])]
+    pub all: bool,
+    /// Seed for the random questions; the same seed replays the same quiz
+    #[arg(long, value_name = "N")]
+    pub seed: Option<u64>,
+    /// Show the answer choices in a random order
+    #[arg(long)]
+    pub shuffle_choices: bool,
+    /// Give partial credit for partly right answers to questions with several correct choices
+    #[arg(long)]
+    pub partial_credit: bool,
+    /// Grade nothing until the whole exam is submitted
+    #[arg(long, conflicts_with_all = ["resume", "tui"])]
+    pub exam: bool,
+    /// Start at LEVEL and move up or down a level as you answer
+    #[arg(long, value_name = "LEVEL", conflicts_with_all = ["exam", "resume"])]
+    pub adaptive: Option<Level>,
+    /// Seconds allowed per question
+    #[arg(long, value_name = "SECS", value_parser = seconds)]
+    pub time_limit: Option<Duration>,
+    /// Seconds allowed for the whole quiz
+    #[arg(long, value_name = "SECS", value_parser = seconds)]
+    pub total_time: Option<Duration>,
+    /// How points are counted: plain, level_weighted, negative_marking, speed_bonus or streak
+    #[arg(long, value_name = "NAME", default_value = "plain")]
+    pub scoring: Scoring,
+    /// Correct answers needed to pass, such as 7, 80% or ownership=80%; repeat for several
+    #[arg(long = "pass-mark", value_name = "MARK")]
+    pub pass_marks: Vec<PassMark>,
+    /// Save progress to FILE after every answer, and pick an unfinished quiz back up from it
+    #[arg(long, value_name = "FILE")]
+    pub resume: Option<PathBuf>,
+    #[command(flatten)]
+    pub interface: InterfaceArgs,
+    #[command(flatten)]
+    pub history: HistoryArgs,
+    #[command(flatten)]
+    pub packs: PackArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct ReviewArgs {
+    /// File the review schedule is kept in
+    #[arg(long, value_name = "FILE", default_value = REVIEW_STATE)]
+    pub state: PathBuf,
+    /// How many questions never seen before to add
+    #[arg(long, value_name = "N", default_value_t = NEW_PER_DAY)]
+    pub new: usize,
+    #[command(flatten)]
+    pub interface: InterfaceArgs,
+    #[command(flatten)]
+    pub history: HistoryArgs,
+    #[command(flatten)]
+    pub packs: PackArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct StatsArgs {
+    #[command(flatten)]
+    pub history: HistoryArgs,
+    #[command(flatten)]
+    pub packs: PackArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    #[command(flatten)]
+    pub filters: FilterArgs,
+    /// toml or json; by default taken from the output file, else toml
+    #[arg(long, value_name = "FORMAT")]
+    pub format: Option<Format>,
+    /// Write to FILE instead of standard output
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+    #[command(flatten)]
+    pub packs: PackArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    #[command(flatten)]
+    pub filters: FilterArgs,
+    #[command(flatten)]
+    pub packs: PackArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct ShowArgs {
+    /// The question id
+    pub id: u32,
+    #[command(flatten)]
+    pub packs: PackArgs,
+}
+
+/// Parses `beginner` or an inclusive range such as `intro..beginner_plus`.
+fn parse_levels(input: &str) -> Result<RangeInclusive<Level>, String> {
+    match input.split_once("..") {
+        Some((min, max)) => {
+            let min: Level = min.parse()?;
+            let max: Level = max.parse()?;
+            if min > max {
+                return Err(format!("level range {:?} is empty", input));
+            }
+            Ok(min..=max)
+        }
+        None => {
+            let level: Level = input.parse()?;
+            Ok(level..=level)
+        }
+    }
+}
+
+fn positive(input: &str) -> Result<usize, String> {
+    match input.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err("expected a positive number".to_string()),
+    }
+}
+
+fn seconds(input: &str) -> Result<Duration, String> {
+    positive(input).map(|secs| Duration::from_secs(secs as u64))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("cli").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_play_is_the_default() {
+        let cli = parse(&["--tag", "ownership", "--count", "3", "pack.toml"]).unwrap();
+
+        assert!(cli.command.is_none());
+        assert_eq!(cli.play.filters.query(), Query::new().tag("ownership").count(3));
+        assert_eq!(cli.play.packs.packs, vec![PathBuf::from("pack.toml")]);
+        assert_eq!(cli.play.interface.protocol, Protocol::Plain);
+        assert!(cli.play.pass_marks.is_empty());
+    }
+
+    #[test]
+    fn test_subcommands() {
+        let cli = parse(&["show", "7"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Show(ShowArgs { id: 7, .. }))));
+
+        let cli = parse(&["play", "--level", "intro..beginner", "--ids", "1,2"]).unwrap();
+        let Some(Command::Play(play)) = cli.command else { panic!("expected play") };
+        assert_eq!(play.filters.query(), Query::new().ids(vec![1, 2]).levels(Level::Intro..=Level::Beginner));
+
+        let cli = parse(&["--pass-mark", "80%", "--pass-mark", "ownership=2"]).unwrap();
+        let marks: Vec<String> = cli.play.pass_marks.iter().map(|mark| mark.to_string()).collect();
+        assert_eq!(marks, ["80%", "ownership=2"]);
+    }
+
+    #[test]
+    fn test_rejects_bad_values() {
+        assert!(parse(&["--count", "0"]).is_err());
+        assert!(parse(&["--level", "beginner..intro"]).is_err());
+        assert!(parse(&["--exam", "--resume", "progress.json"]).is_err());
+        assert!(parse(&["--scoring", "golf"]).is_err());
+        assert!(parse(&["--pass-mark", "150%"]).is_err());
+        assert!(parse(&["--tui", "--exam"]).is_err());
+        assert!(parse(&["review", "--tui", "--protocol", "jsonl"]).is_err());
+    }
+
+    #[test]
+    fn test_count_picks_at_random() {
+        let cli = parse(&["list", "--tag", "basics"]).unwrap();
+        let Some(Command::List(list)) = cli.command else { panic!("expected list") };
+        assert_eq!(list.filters.random_query().seed, None);
+
+        let cli = parse(&["export", "--count", "2"]).unwrap();
+        let Some(Command::Export(export)) = cli.command else { panic!("expected export") };
+        assert!(export.filters.random_query().seed.is_some());
+    }
+}
@@ -0,0 +1,121 @@
+use std::io::{self, BufRead, Write};
+
+use rust_quiz::engine::{AnswerResult, Attempt, QuizState, SystemClock};
+use rust_quiz::protocol::{check_response, Command, Event};
+use rust_quiz::quiz::Quiz;
+
+/// Writes `event` as one line, flushed so the client sees it at once.
+pub fn emit(output: &mut impl Write, event: &Event) -> io::Result<()> {
+    writeln!(output, "{}", event.to_json())?;
+    output.flush()
+}
+
+/// Ends the program when the client can no longer be talked to.
+pub fn abort<T>(err: io::Error) -> T {
+    crate::fail(&format!("cannot talk to the client: {}", err))
+}
+
+/// Asks the questions over the JSON-lines protocol, like the plain
+/// `run_practice`: a `question` event for each, then a `feedback` event once
+/// an `answer` command is graded. A command that cannot be used gets an
+/// `error` event and the question keeps waiting.
+///
+/// Returns whether the quiz ran to the end, rather than the client quitting
+/// or closing its input.
+pub fn practice(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    quizzes: &mut Vec<Quiz>,
+    state: &mut QuizState,
+    total: usize,
+    mut on_answer: impl FnMut(&Quiz, &Attempt, &mut Vec<Quiz>) -> Option<String>,
+) -> io::Result<bool> {
+    while let Some(quiz) = state.current_quiz(quizzes).cloned() {
+        if state.question_started_at.is_none() {
+            state.start_question(&SystemClock);
+        }
+        emit(output, &Event::question(&quiz, state, total.max(quizzes.len()), &SystemClock))?;
+
+        let response = loop {
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                return Ok(false);
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            match Command::from_json(&line) {
+                Ok(Command::Answer { response }) => match check_response(&quiz, &response) {
+                    Ok(()) => break response,
+                    Err(message) => emit(output, &Event::error(message))?,
+                },
+                Ok(Command::Quit) => return Ok(false),
+                Err(err) => emit(output, &Event::error(format!("invalid command: {}", err)))?,
+            }
+        };
+
+        state.select(response);
+        // The whole quiz ran out of time.
+        if state.submit_selection_at(quizzes, &SystemClock) == AnswerResult::Finished {
+            break;
+        }
+        let note = state.history.last().and_then(|attempt| on_answer(&quiz, attempt, quizzes));
+        if let Some(event) = Event::feedback(&quiz, state, note) {
+            emit(output, &event)?;
+        }
+        state.next(quizzes);
+    }
+    Ok(true)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_quiz::quiz::quiz_bank;
+
+    fn run(commands: &str) -> (bool, Vec<Event>, QuizState) {
+        let mut quizzes: Vec<Quiz> = quiz_bank().into_iter().take(2).collect();
+        let mut state = QuizState::new();
+        let mut output = Vec::new();
+        let finished = practice(&mut commands.as_bytes(), &mut output, &mut quizzes, &mut state, 2, |_, _, _| None).unwrap();
+        let events = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| Event::from_json(line).unwrap())
+            .collect();
+        (finished, events, state)
+    }
+
+    #[test]
+    fn test_plays_a_quiz() {
+        let commands = r#"
+            not json
+            {"command": "answer", "response": {"choice": 3}}
+            {"command": "answer", "response": {"text": "mut"}}
+            {"command": "answer", "response": {"choice": 0}}
+        "#;
+        let (finished, events, state) = run(commands);
+
+        assert!(finished);
+        assert_eq!(events.len(), 6);
+        assert!(matches!(&events[0], Event::Question { number: 1, total: 2, choices, .. } if choices[3].text == ".rs"));
+        assert!(matches!(&events[1], Event::Error { message } if message.starts_with("invalid command")));
+        assert!(matches!(&events[2], Event::Feedback { result: AnswerResult::Correct, .. }));
+        assert!(matches!(&events[3], Event::Question { number: 2, .. }));
+        assert!(matches!(&events[4], Event::Error { .. }));
+        assert!(matches!(&events[5], Event::Feedback { result: AnswerResult::Wrong, .. }));
+        assert_eq!(state.score, 1);
+    }
+
+    #[test]
+    fn test_quit_and_closed_input_stop_early() {
+        let (finished, events, _) = run(r#"{"command": "quit"}"#);
+        assert!(!finished);
+        assert_eq!(events.len(), 1);
+
+        let (finished, _, state) = run(r#"{"command": "answer", "response": {"choice": 3}}"#);
+        assert!(!finished);
+        assert_eq!(state.history.len(), 1);
+    }
+}
@@ -1,16 +1,381 @@
-use std::io::{self, Write};
+mod args;
+mod jsonl;
+mod tui;
 
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::Duration;
+
+use clap::Parser;
 use rust_quiz::quiz;
-use rust_quiz::quiz::quiz_bank;
-use rust_quiz::engine::{QuizState, AnswerResult};
+use rust_quiz::quiz::{Answer, Matching};
+use rust_quiz::loader::{default_pack, load_bank, load_pack, pack_to_string, Format};
+use rust_quiz::validate::{has_errors, validate};
+use rust_quiz::engine::{
+    describe_response, describe_solution, AnswerResult, Attempt, Clock, Credit, QuizState, Response, SystemClock,
+    Timeout,
+};
+
+use rust_quiz::select::{pick_random, Query};
+use rust_quiz::session::Session;
+use rust_quiz::schedule::{day, Schedule};
+use rust_quiz::adaptive::Adaptive;
+use rust_quiz::stats::{History, Stats, Tally};
+use rust_quiz::highlight::{self, Kind};
+use rust_quiz::scoring::Scoring;
+use rust_quiz::protocol::Event;
+use rust_quiz::gate::Verdict;
+
+use args::{Cli, Command, ExportArgs, InterfaceArgs, ListArgs, PlayArgs, Protocol, ReviewArgs, ShowArgs, StatsArgs};
+
+/// Exit status of a quiz that misses a `--pass-mark`, apart from errors (1)
+/// and bad usage (2).
+const NOT_PASSED: i32 = 3;
+
+/// Exit status for leaving a quiz before the end: [`NOT_PASSED`] once a
+/// pass mark is set, since an unfinished quiz has not passed it.
+static LEAVE_STATUS: AtomicI32 = AtomicI32::new(0);
+
+/// Ends the program from the middle of a quiz.
+fn exit_early() -> ! {
+    process::exit(LEAVE_STATUS.load(Ordering::Relaxed));
+}
+
+/// Prints `message` and reads a line. Once input runs out nothing more can
+/// be answered, so the program ends there; a `--resume` file already holds
+/// the progress.
+fn prompt(message: &str) -> String {
+    print!("{}", message);
+    let _ = io::stdout().flush();
+
+    let mut input = String::new();
+    match io::stdin().read_line(&mut input) {
+        Ok(0) => {
+            println!("\n👋 Input ended, leaving the quiz.");
+            exit_early();
+        }
+        Ok(_) => input,
+        Err(err) => fail(&format!("cannot read input: {}", err)),
+    }
+}
+
+/// Parses "1,3" or "1 3" into zero-based choice indices.
+fn parse_choices(input: &str, count: usize) -> Option<Vec<usize>> {
+    let mut choices = Vec::new();
+    for part in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        if part.is_empty() {
+            continue;
+        }
+        match part.parse::<usize>() {
+            Ok(n) if n > 0 && n <= count => choices.push(n - 1),
+            _ => return None,
+        }
+    }
+    if choices.is_empty() { None } else { Some(choices) }
+}
+
+/// What to ask for on the first line of an answer.
+fn answer_prompt(quiz: &quiz::Quiz) -> String {
+    match &quiz.correct {
+        Answer::Text(_) => "\nYour answer: ".to_string(),
+        Answer::Blanks { blanks } => format!("Blank 1 of {}: ", blanks.len()),
+        Answer::Order { .. } => format!(
+            "\nEnter the line numbers in the right order, comma-separated (1-{}): ",
+            quiz.choices.len()
+        ),
+        Answer::Multiple(_) => format!(
+            "\nSelect all that apply, comma-separated (1-{}): ",
+            quiz.choices.len()
+        ),
+        Answer::Single(_) => format!("\nYour answer (1-{}): ", quiz.choices.len()),
+    }
+}
+
+/// Prompts for an answer in the form the question needs. Returns `None`
+/// when the input cannot be understood.
+fn read_response(quiz: &quiz::Quiz, order: &[usize]) -> Option<Response> {
+    read_response_from(quiz, order, &prompt(&answer_prompt(quiz)))
+}
+
+/// Turns `input`, typed after [`answer_prompt`], into a response, asking
+/// for any further blanks.
+///
+/// Numbers typed by the learner are positions in the displayed `order` and
+/// are mapped back to authored choice indices.
+fn read_response_from(quiz: &quiz::Quiz, order: &[usize], input: &str) -> Option<Response> {
+    let parse = |input: &str| {
+        parse_choices(input.trim(), order.len())
+            .map(|shown| shown.into_iter().map(|n| order[n]).collect::<Vec<_>>())
+    };
+
+    match &quiz.correct {
+        Answer::Text(_) => {
+            let input = input.trim();
+            (!input.is_empty()).then(|| Response::Text(input.to_string()))
+        }
+        Answer::Blanks { blanks } => {
+            let mut filled = Vec::new();
+            for i in 0..blanks.len() {
+                let input = if i == 0 {
+                    input.to_string()
+                } else {
+                    prompt(&format!("Blank {} of {}: ", i + 1, blanks.len()))
+                };
+                let input = input.trim();
+                if input.is_empty() {
+                    return None;
+                }
+                filled.push(input.to_string());
+            }
+            Some(Response::Blanks(filled))
+        }
+        Answer::Order { .. } => {
+            // Every line must be placed exactly once.
+            let order = parse(input)?;
+            let mut placed = order.clone();
+            placed.sort_unstable();
+            placed.dedup();
+            (placed.len() == order.len() && order.len() == quiz.choices.len())
+                .then_some(Response::Order(order))
+        }
+        Answer::Multiple(_) => parse(input).map(Response::Choices),
+        Answer::Single(_) => match parse(input)?.as_slice() {
+            [choice] => Some(Response::Choice(*choice)),
+            _ => None,
+        },
+    }
+}
+
+/// Shows the expected answer for question kinds where the explanation alone
+/// does not make it obvious.
+fn print_solution(quiz: &quiz::Quiz) {
+    let shown = |text: &quiz::TextAnswer| match text.matching {
+        Matching::Regex => None,
+        _ => text.accepted.first().cloned(),
+    };
+
+    match &quiz.correct {
+        Answer::Text(text) => {
+            if let Some(accepted) = shown(text) {
+                println!("👉 Answer: {}", accepted);
+            }
+        }
+        Answer::Blanks { blanks } => {
+            for (i, blank) in blanks.iter().enumerate() {
+                if let Some(accepted) = shown(blank) {
+                    println!("👉 Blank {}: {}", i + 1, accepted);
+                }
+            }
+        }
+        Answer::Order { order } => {
+            println!("👉 Correct order:");
+            for i in order {
+                if let Some(line) = quiz.choices.get(*i) {
+                    println!("   {}", line);
+                }
+            }
+        }
+        Answer::Single(_) | Answer::Multiple(_) => {}
+    }
+}
+
+/// Whether to color output: only on a terminal, and not when `NO_COLOR` is
+/// set.
+fn use_color() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()
+}
+
+/// The ANSI escape that colors a token of this kind.
+fn ansi(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Plain => "",
+        Kind::Keyword => "\x1b[35m",
+        Kind::Type | Kind::Number => "\x1b[33m",
+        Kind::Function => "\x1b[34m",
+        Kind::Macro | Kind::Lifetime => "\x1b[36m",
+        Kind::String => "\x1b[32m",
+        Kind::Comment | Kind::Attribute => "\x1b[90m",
+        Kind::Blank => "\x1b[1;4m",
+    }
+}
+
+/// Prints `code` with line numbers, highlighted when colors are on.
+fn print_code(code: &str) {
+    let color = use_color();
+    let lines = highlight::lines(code);
+    let width = lines.len().to_string().len();
+    for (n, tokens) in lines.iter().enumerate() {
+        let text: String = tokens
+            .iter()
+            .map(|token| match ansi(token.kind) {
+                escape if color && !escape.is_empty() => format!("{}{}\x1b[0m", escape, token.text),
+                _ => token.text.to_string(),
+            })
+            .collect();
+        if color {
+            println!("\x1b[90m{:>width$} │\x1b[0m {}", n + 1, text);
+        } else {
+            println!("{:>width$} │ {}", n + 1, text);
+        }
+    }
+}
+
+fn print_question(quiz: &quiz::Quiz, order: &[usize]) {
+    println!("{}", quiz.question);
+
+    if let Some(code) = &quiz.code {
+        println!();
+        print_code(code);
+        if !order.is_empty() {
+            println!();
+        }
+    }
 
-use rand::seq::SliceRandom;
-use rand::rng;
+    for (n, i) in order.iter().enumerate() {
+        println!("  {}. {}", n + 1, quiz.choices[*i]);
+    }
+}
+
+/// One entry per question: `*` answered, `!` flagged, the current one in
+/// brackets.
+fn palette(state: &QuizState, total: usize) -> String {
+    (0..total)
+        .map(|i| {
+            let marks = format!(
+                "{}{}{}",
+                i + 1,
+                if state.is_answered(i) { "*" } else { "" },
+                if state.is_flagged(i) { "!" } else { "" }
+            );
+            if i == state.current { format!("[{}]", marks) } else { marks }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn confirm(message: &str) -> bool {
+    matches!(prompt(message).trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+/// Plays `quizzes` as an exam: answers can be skipped, revisited, flagged
+/// and changed, and are only graded once the exam is submitted.
+fn run_exam(quizzes: &[quiz::Quiz], state: &mut QuizState) {
+    println!("\n📝 Exam mode: type an answer, or s = skip, b = back, f = flag, g N = go to question N, x = submit exam.");
+    state.start_question(&SystemClock);
+
+    while !state.finished {
+        let quiz = &quizzes[state.current];
+        println!("\nQuestions: {}", palette(state, quizzes.len()));
+        println!(
+            "\n[{}/{}] [{}] {}{}",
+            state.current + 1,
+            quizzes.len(),
+            quiz.id,
+            quiz.title,
+            if state.is_flagged(state.current) { " 🚩" } else { "" }
+        );
+        let order = state.choice_order(quiz);
+        print_question(quiz, &order);
+        if let Some(response) = &state.selection {
+            println!("✏️  Current answer: {}", describe_response(quiz, response));
+        }
+
+        print_countdown(state);
+        let input = prompt(&answer_prompt(quiz));
+        if state.check_time(quizzes, &SystemClock) == Some(Timeout::Total) {
+            println!("⏰ Time is up! The exam was submitted as it stood.");
+            break;
+        }
+        let command = input.trim().to_ascii_lowercase();
+        let go_to = command
+            .strip_prefix("g ")
+            .and_then(|n| n.trim().parse::<usize>().ok())
+            .filter(|n| (1..=quizzes.len()).contains(n));
+        if let Some(n) = go_to {
+            state.go_to(quizzes, n - 1);
+            continue;
+        }
+
+        match command.as_str() {
+            "s" => state.skip(quizzes),
+            "b" => state.back(quizzes),
+            "f" => state.toggle_flag(),
+            "x" => {
+                let unanswered = state.unanswered(quizzes);
+                let flagged = state.flagged.len();
+                let question = if unanswered.is_empty() && flagged == 0 {
+                    "Submit the exam? (y/n): ".to_string()
+                } else {
+                    format!(
+                        "{} unanswered and {} flagged question(s). Submit anyway? (y/n): ",
+                        unanswered.len(),
+                        flagged
+                    )
+                };
+                if confirm(&question) {
+                    state.submit_exam(quizzes);
+                }
+            }
+            _ => match read_response_from(quiz, &order, &input) {
+                Some(response) => {
+                    state.select(response);
+                    match state.next_unanswered(quizzes) {
+                        Some(next) => state.go_to(quizzes, next),
+                        None => {
+                            if confirm("\nAll questions answered. Submit the exam? (y/n): ") {
+                                state.submit_exam(quizzes);
+                            }
+                        }
+                    }
+                }
+                None => println!("❌ Invalid input, try again."),
+            },
+        }
+    }
+}
+
+/// Prints `text` after `label`, putting multi-line answers on their own
+/// indented lines.
+fn print_labeled(label: &str, text: &str) {
+    if text.contains('\n') {
+        println!("   {}", label);
+        for line in text.lines() {
+            println!("      {}", line);
+        }
+    } else {
+        println!("   {} {}", label, text);
+    }
+}
 
-fn pick_random_quizzes(mut quizzes: Vec<quiz::Quiz>, count: usize) -> Vec<quiz::Quiz> {
-    let mut rng = rng();
-    quizzes.shuffle(&mut rng);
-    quizzes.into_iter().take(count).collect()
+fn print_review(quizzes: &[quiz::Quiz], state: &QuizState) {
+    println!("\n📋 Review");
+    for attempt in &state.history {
+        let Some(quiz) = quizzes.iter().find(|q| q.id == attempt.quiz_id) else {
+            continue;
+        };
+        let mark = match attempt.result {
+            AnswerResult::Correct => "✅",
+            AnswerResult::Partial => "🟡",
+            _ => "❌",
+        };
+        println!("\n{} [{}] {}", mark, quiz.id, quiz.title);
+        if attempt.timed_out {
+            println!("   ⏰ Ran out of time");
+        } else {
+            print_labeled("Your answer:", &describe_response(quiz, &attempt.response));
+        }
+        if attempt.result != AnswerResult::Correct {
+            print_labeled("Correct answer:", &describe_solution(quiz));
+        }
+        if let Some(ms) = attempt.time_taken {
+            println!("   ⏱  {:.1}s", ms as f64 / 1000.0);
+        }
+        println!("   👉 {}", quiz.explanation);
+    }
 }
 
 fn select_mode() -> u8 {
@@ -19,14 +384,8 @@ fn select_mode() -> u8 {
         println!("Select mode:");
         println!("1. Random 5 questions");
         println!("2. Play all questions (in order)");
-        print!("Your choice (1 or 2): ");
-
-        io::stdout().flush().unwrap();
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
-
-        match input.trim() {
+        match prompt("Your choice (1 or 2): ").trim() {
             "1" => return 1,
             "2" => return 2,
             _ => {
@@ -36,65 +395,608 @@ fn select_mode() -> u8 {
     }
 }
 
-fn main() {
-    println!("🦀 Welcome to RustQuiz!");
-    println!("-----------------------");
+fn now_millis() -> u64 {
+    SystemClock.now_millis()
+}
+
+/// Prints the time left on the clock, if the quiz is timed.
+fn print_countdown(state: &QuizState) {
+    if let Some(left) = state.time_left(&SystemClock) {
+        println!("⏱  {}s left", left.as_secs());
+    }
+}
+
+/// The unfinished session saved at `path`, if there is one.
+fn load_session(path: &Path) -> Option<Session> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+        Err(err) => fail(&format!("cannot read {}: {}", path.display(), err)),
+    };
+    match Session::from_json(&text) {
+        Ok(session) if session.is_finished() => None,
+        Ok(session) => Some(session),
+        Err(err) => fail(&format!("{}: {}", path.display(), err)),
+    }
+}
+
+fn save_session(path: &Path, session: &Session) {
+    if let Err(err) = fs::write(path, session.to_json()) {
+        eprintln!("⚠️  cannot save progress to {}: {}", path.display(), err);
+    }
+}
+
+fn load_schedule(path: &Path) -> Schedule {
+    match fs::read_to_string(path) {
+        Ok(text) => Schedule::from_json(&text).unwrap_or_else(|err| fail(&format!("{}: {}", path.display(), err))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Schedule::new(),
+        Err(err) => fail(&format!("cannot read {}: {}", path.display(), err)),
+    }
+}
+
+fn save_schedule(path: &Path, schedule: &Schedule) {
+    if let Err(err) = fs::write(path, schedule.to_json()) {
+        eprintln!("⚠️  cannot save review state to {}: {}", path.display(), err);
+    }
+}
+
+fn load_history(path: &Path) -> History {
+    match fs::read_to_string(path) {
+        Ok(text) => History::from_json(&text).unwrap_or_else(|err| fail(&format!("{}: {}", path.display(), err))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => History::new(),
+        Err(err) => fail(&format!("cannot read {}: {}", path.display(), err)),
+    }
+}
+
+/// Adds a finished quiz's answers to the history file.
+fn log_history(path: &Path, attempts: &[Attempt]) {
+    let mut history = load_history(path);
+    history.add(attempts, now_millis());
+    if let Err(err) = fs::write(path, history.to_json()) {
+        eprintln!("⚠️  cannot save statistics to {}: {}", path.display(), err);
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("❌ {}", message);
+    process::exit(1);
+}
+
+/// The built-in questions followed by those in `packs`.
+fn bank(packs: &[PathBuf]) -> Vec<quiz::Quiz> {
+    load_bank(packs).unwrap_or_else(|err| fail(&err.to_string()))
+}
 
-    let mode = select_mode();
+/// Validates the built-in bank together with the given packs and returns the
+/// process exit code: 1 if any error was found, 0 otherwise.
+fn lint(pack_paths: &[PathBuf]) -> i32 {
+    let mut sources = vec![("built-in".to_string(), default_pack())];
+    for path in pack_paths {
+        match load_pack(path) {
+            Ok(quizzes) => sources.push((path.display().to_string(), quizzes)),
+            Err(err) => {
+                eprintln!("❌ {}", err);
+                return 1;
+            }
+        }
+    }
 
-    let all_quizzes = quiz_bank();
+    // Validate everything at once so ids clashing across packs are caught.
+    let all_quizzes: Vec<quiz::Quiz> = sources.iter().flat_map(|(_, q)| q.iter().cloned()).collect();
+    let diagnostics = validate(&all_quizzes);
 
-    let quizzes = match mode {
-        1 => {
-            let count = 5.min(all_quizzes.len());
-            pick_random_quizzes(all_quizzes, count)
+    for diagnostic in &diagnostics {
+        let mut index = diagnostic.index;
+        for (label, quizzes) in &sources {
+            if index < quizzes.len() {
+                println!("{}: {}", label, diagnostic);
+                break;
+            }
+            index -= quizzes.len();
         }
-        2 => all_quizzes,
-        _ => unreachable!(),
-    };
+    }
+
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    let warnings = diagnostics.len() - errors;
+    if diagnostics.is_empty() {
+        println!("✅ {} questions checked, no problems found.", all_quizzes.len());
+    } else {
+        println!(
+            "{} questions checked: {} error(s), {} warning(s).",
+            all_quizzes.len(),
+            errors,
+            warnings
+        );
+    }
+
+    if has_errors(&diagnostics) { 1 } else { 0 }
+}
+
+fn print_tally(label: &str, tally: &Tally) {
+    println!(
+        "{:<20} {:>8} {:>8} {:>8.0}%",
+        label,
+        tally.answered,
+        tally.correct,
+        tally.accuracy()
+    );
+}
+
+/// Prints accuracy per topic and level from the history file, weakest
+/// topics first. Extra packs are needed to know the tags of their questions.
+fn stats(args: StatsArgs) {
+    let history = load_history(&args.history.path);
+    let stats = Stats::from_records(&bank(&args.packs.packs), &history.records);
+    if stats.overall.answered == 0 {
+        println!("No answers recorded yet. Finish a quiz and try again.");
+        return;
+    }
+
+    println!(
+        "📊 {} answers, {} correct ({:.0}%)\n",
+        stats.overall.answered,
+        stats.overall.correct,
+        stats.overall.accuracy()
+    );
+    println!("{:<20} {:>8} {:>8} {:>9}", "Topic", "Answered", "Correct", "Accuracy");
+    for (tag, tally) in stats.weakest_tags() {
+        print_tally(tag, &tally);
+    }
+    println!("\n{:<20} {:>8} {:>8} {:>9}", "Level", "Answered", "Correct", "Accuracy");
+    for (level, tally) in &stats.by_level {
+        print_tally(&level.to_string(), tally);
+    }
+}
+
+/// Plays today's spaced-repetition review.
+fn review(args: ReviewArgs) {
+    let path = args.state.as_path();
+    let mut schedule = load_schedule(path);
+    let today = day(now_millis());
+    let mut quizzes = schedule.due(&bank(&args.packs.packs), today, args.new);
+
+    let mut frontend = Frontend::open(&args.interface);
+    frontend.say("🦀 RustQuiz daily review\n-----------------------");
+    if quizzes.is_empty() {
+        match schedule.next_due() {
+            Some(due) => frontend.say(&format!("✅ Nothing due today. Next review in {} day(s).", due.saturating_sub(today))),
+            None => frontend.say("✅ Nothing to review."),
+        }
+        if let Frontend::Jsonl = frontend {
+            // A client still gets a result, with no answers.
+            frontend.finish(quizzes, QuizState::new(), Scoring::Plain, "", true);
+        }
+        return;
+    }
+    frontend.say(&format!("📚 {} question(s) due today.", quizzes.len()));
 
     let mut state = QuizState::new();
+    let on_answer = |quiz: &quiz::Quiz, attempt: &Attempt, _: &mut Vec<quiz::Quiz>| -> Option<String> {
+        schedule.record(quiz.id, attempt.result, today);
+        save_schedule(path, &schedule);
+        None
+    };
+    let total = quizzes.len();
+    frontend.practice(&mut quizzes, &mut state, total, Scoring::Plain, on_answer);
 
-    while let Some(quiz) = state.current_quiz(&quizzes) {
-        println!("\n[{}] {}", quiz.id, quiz.title);
-        println!("{}", quiz.question);
+    log_history(&args.history.path, &state.history);
+    let mut summary = format!("\n🎉 Review finished!\nScore: {}/{}", state.score, quizzes.len());
+    if let Some(due) = schedule.next_due() {
+        summary += &format!("\n📅 Next review in {} day(s).", due.saturating_sub(today));
+    }
+    frontend.finish(quizzes, state, Scoring::Plain, &summary, true);
+}
 
-        if let Some(code) = quiz.code {
-            println!("\n--- code ---");
-            println!("{}", code);
-            println!("------------");
+/// Where questions are asked and answered.
+enum Frontend {
+    Plain,
+    Screen(tui::Screen),
+    Jsonl,
+}
+
+impl Frontend {
+    /// The JSON-lines protocol if that was asked for, the full-screen
+    /// interface if that was asked for and the terminal can show it, and
+    /// plain prompts otherwise.
+    fn open(args: &InterfaceArgs) -> Self {
+        if args.protocol == Protocol::Jsonl {
+            return Frontend::Jsonl;
+        }
+        if !args.tui {
+            return Frontend::Plain;
+        }
+        if !tui::supported() {
+            eprintln!("⚠️  This terminal cannot show the full-screen interface; using plain prompts.");
+            return Frontend::Plain;
+        }
+        match tui::Screen::enter() {
+            Ok(screen) => Frontend::Screen(screen),
+            Err(err) => {
+                eprintln!("⚠️  cannot start the full-screen interface ({}); using plain prompts.", err);
+                Frontend::Plain
+            }
+        }
+    }
+
+    /// Prints a message for the person at the terminal. Standard output
+    /// carries the protocol in JSON-lines mode, so it goes to standard error
+    /// there.
+    fn say(&self, message: &str) {
+        match self {
+            Frontend::Jsonl => eprintln!("{}", message),
+            _ => println!("{}", message),
         }
+    }
+
+    /// Asks the questions; see [`run_practice`]. A client leaving the
+    /// JSON-lines protocol early ends the program, as running out of input
+    /// does with plain prompts.
+    fn practice(
+        &mut self,
+        quizzes: &mut Vec<quiz::Quiz>,
+        state: &mut QuizState,
+        total: usize,
+        scoring: Scoring,
+        on_answer: impl FnMut(&quiz::Quiz, &Attempt, &mut Vec<quiz::Quiz>) -> Option<String>,
+    ) {
+        match self {
+            Frontend::Plain => run_practice(quizzes, state, on_answer),
+            Frontend::Screen(screen) => screen
+                .practice(quizzes, state, total, scoring, on_answer)
+                .unwrap_or_else(tui::abort),
+            Frontend::Jsonl => {
+                let finished = jsonl::practice(&mut io::stdin().lock(), &mut io::stdout(), quizzes, state, total, on_answer)
+                    .unwrap_or_else(jsonl::abort);
+                if !finished {
+                    exit_early();
+                }
+            }
+        }
+    }
 
-        for (i, choice) in quiz.choices.iter().enumerate() {
-            println!("  {}. {}", i + 1, choice);
+    /// Shows how a finished quiz went: `summary` and a review offering to
+    /// retry mistakes, or in JSON-lines mode the `result` event. Without
+    /// `retries` nothing waits for input, and the full-screen interface is
+    /// left for the printed review.
+    fn finish(self, quizzes: Vec<quiz::Quiz>, state: QuizState, scoring: Scoring, summary: &str, retries: bool) {
+        match self {
+            Frontend::Screen(screen) if retries => {
+                review_on_screen(screen, quizzes, state, scoring);
+                println!("{}", summary);
+            }
+            Frontend::Jsonl => {
+                jsonl::emit(&mut io::stdout(), &Event::result(&quizzes, &state, scoring)).unwrap_or_else(jsonl::abort);
+            }
+            plain_or_screen => {
+                drop(plain_or_screen);
+                println!("{}", summary);
+                print_review(&quizzes, &state);
+                if retries {
+                    offer_retries(quizzes, state);
+                }
+            }
         }
+    }
+}
+
+/// The full-screen counterpart of [`print_review`] and [`offer_retries`]:
+/// the review table, from which the missed questions can be played again
+/// until the learner is done. Leaves full-screen mode at the end.
+fn review_on_screen(mut screen: tui::Screen, mut quizzes: Vec<quiz::Quiz>, mut state: QuizState, scoring: Scoring) {
+    while screen.review(&quizzes, &state, scoring).unwrap_or_else(tui::abort) {
+        (quizzes, state) = state.retry_mistakes(&quizzes);
+        let total = quizzes.len();
+        screen
+            .practice(&mut quizzes, &mut state, total, scoring, |_, _, _| None)
+            .unwrap_or_else(tui::abort);
+    }
+}
 
-        print!("\nYour answer (1-{}): ", quiz.choices.len());
-        io::stdout().flush().unwrap();
+/// Offers the questions missed in a finished quiz again, round after round,
+/// until none are left or the learner declines. Retries are practice and
+/// are not logged, so the statistics count each question once per quiz.
+fn offer_retries(mut quizzes: Vec<quiz::Quiz>, mut state: QuizState) {
+    loop {
+        let (missed, fresh) = state.retry_mistakes(&quizzes);
+        if missed.is_empty() || !confirm(&format!("\n🔁 Retry the {} question(s) you missed? (y/n): ", missed.len())) {
+            return;
+        }
+        quizzes = missed;
+        state = fresh;
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
+        if state.exam {
+            run_exam(&quizzes, &mut state);
+        }
+        run_practice(&mut quizzes, &mut state, |_, _, _| None);
 
-        let choice = match input.trim().parse::<usize>() {
-            Ok(n) if n > 0 && n <= quiz.choices.len() => n - 1,
-            _ => {
+        println!("\n🎉 Retry finished!");
+        println!("Score: {}/{}", state.score, quizzes.len());
+        print_review(&quizzes, &state);
+    }
+}
+
+/// Asks the questions one at a time with feedback after each answer.
+/// `on_answer` sees every graded answer, to save progress as it happens or
+/// add the next question to `quizzes`; a note it returns is printed.
+fn run_practice(
+    quizzes: &mut Vec<quiz::Quiz>,
+    state: &mut QuizState,
+    mut on_answer: impl FnMut(&quiz::Quiz, &Attempt, &mut Vec<quiz::Quiz>) -> Option<String>,
+) {
+    while let Some(quiz) = state.current_quiz(quizzes).cloned() {
+        println!("\n[{}] {}", quiz.id, quiz.title);
+        if state.question_started_at.is_none() {
+            state.start_question(&SystemClock);
+        }
+        let order = state.choice_order(&quiz);
+        print_question(&quiz, &order);
+        print_countdown(state);
+
+        let response = read_response(&quiz, &order);
+        let out_of_time = state.time_left(&SystemClock) == Some(Duration::ZERO);
+        match response {
+            Some(response) => state.select(response),
+            // Late input is submitted anyway, to be recorded as timed out.
+            None if out_of_time => {}
+            None => {
                 println!("❌ Invalid input, try again.");
                 continue;
             }
-        };
+        }
+
+        let result = state.submit_selection_at(quizzes, &SystemClock);
+        if state.feedback.as_ref().is_some_and(|f| f.timed_out) {
+            println!("⏰ Time's up! That answer came too late.");
+            print_solution(&quiz);
+            println!("👉 Explanation: {}", quiz.explanation);
+        } else {
+            match result {
+                AnswerResult::Correct => {
+                    println!("✅ Correct!");
+                }
+                AnswerResult::Partial => {
+                    println!("🟡 Partially correct!");
+                    print_solution(&quiz);
+                    println!("👉 Explanation: {}", quiz.explanation);
+                }
+                AnswerResult::Wrong => {
+                    println!("❌ Wrong!");
+                    print_solution(&quiz);
+                    println!("👉 Explanation: {}", quiz.explanation);
+                }
+                AnswerResult::Finished => {
+                    println!("⏰ Time is up for the whole quiz.");
+                    break;
+                }
+            }
+        }
+
+        if result != AnswerResult::Finished
+            && let Some(attempt) = state.history.last()
+            && let Some(note) = on_answer(&quiz, attempt, quizzes)
+        {
+            println!("{}", note);
+        }
+        state.next(quizzes);
+    }
+}
+
+/// Prints the questions matching the filters, one per line.
+fn list(args: ListArgs) {
+    let quizzes = args.filters.random_query().run(&bank(&args.packs.packs));
+    println!("{:>5}  {:<12}  {:<45}  Tags", "Id", "Level", "Title");
+    for quiz in &quizzes {
+        println!(
+            "{:>5}  {:<12}  {:<45}  {}",
+            quiz.id,
+            quiz.level.to_string(),
+            quiz.title,
+            quiz.tags.join(", ")
+        );
+    }
+    println!("\n{} question(s)", quizzes.len());
+}
+
+/// Prints one question with its answer and explanation.
+fn show(args: ShowArgs) {
+    let bank = bank(&args.packs.packs);
+    let Some(quiz) = bank.iter().find(|q| q.id == args.id) else {
+        fail(&format!("there is no question {}", args.id));
+    };
+    println!("[{}] {} ({}; {})", quiz.id, quiz.title, quiz.level, quiz.tags.join(", "));
+    println!();
+    let order: Vec<usize> = (0..quiz.choices.len()).collect();
+    print_question(quiz, &order);
+    println!();
+    print_labeled("Answer:", &describe_solution(quiz));
+    println!("   👉 {}", quiz.explanation);
+}
+
+/// Writes the questions matching the filters as a question pack.
+fn export(args: ExportArgs) {
+    let quizzes = args.filters.random_query().run(&bank(&args.packs.packs));
+    let format = args
+        .format
+        .or_else(|| args.output.as_deref().and_then(Format::from_path))
+        .unwrap_or(Format::Toml);
+    let text = pack_to_string(&quizzes, format);
+    match &args.output {
+        Some(path) => {
+            if let Err(err) = fs::write(path, text) {
+                fail(&format!("cannot write {}: {}", path.display(), err));
+            }
+            eprintln!("✅ Exported {} question(s) to {}", quizzes.len(), path.display());
+        }
+        None => print!("{}", text),
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    match cli.command {
+        None => play(cli.play),
+        Some(Command::Play(args)) => play(args),
+        Some(Command::Review(args)) => review(args),
+        Some(Command::Stats(args)) => stats(args),
+        Some(Command::Lint(args)) => process::exit(lint(&args.packs)),
+        Some(Command::Export(args)) => export(args),
+        Some(Command::List(args)) => list(args),
+        Some(Command::Show(args)) => show(args),
+    }
+}
 
-        match state.answer(&quizzes, choice) {
-            AnswerResult::Correct => {
-                println!("✅ Correct!");
+fn play(args: PlayArgs) {
+    let jsonl = args.interface.protocol == Protocol::Jsonl;
+    if jsonl && args.exam {
+        fail("exam mode cannot be played over --protocol jsonl");
+    }
+    if !args.pass_marks.is_empty() {
+        LEAVE_STATUS.store(NOT_PASSED, Ordering::Relaxed);
+    }
+    let mut frontend = Frontend::open(&args.interface);
+    frontend.say("🦀 Welcome to RustQuiz!\n-----------------------");
+
+    let all_quizzes = bank(&args.packs.packs);
+    let mut seed = args.seed;
+    let mut query = args.filters.query();
+    let resume_path = args.resume.as_deref();
+
+    let saved = resume_path.and_then(load_session);
+    let mut adaptive = None;
+    let (mut quizzes, mut state, mut session) = if let Some(session) = saved {
+        let (quizzes, state) = session.resume(&all_quizzes).unwrap_or_else(|err| fail(&err.to_string()));
+        frontend.say(&format!(
+            "📂 Resuming: question {} of {}, score {} so far.",
+            state.current + 1,
+            quizzes.len(),
+            state.score
+        ));
+        (quizzes, state, session)
+    } else {
+        let quizzes = if let Some(start) = args.adaptive {
+            let seed = *seed.get_or_insert_with(rand::random);
+            frontend.say(&format!("🎲 Seed: {} (replay this quiz with --seed {})", seed, seed));
+            // The whole matching pool; the level moves within it.
+            let pool = Query { count: None, ..query.clone() }.run(&all_quizzes);
+            let length = query.count.unwrap_or(10);
+            let mut driver = Adaptive::new(pool, start).seed(seed).length(length);
+            let first = driver.first();
+            frontend.say(&format!("📶 Starting at level {}.", driver.level()));
+            adaptive = Some(driver);
+            first
+        } else if args.all || query != Query::new() {
+            // A count asks for a random selection; otherwise keep bank order
+            // unless a seed was given.
+            if query.count.is_some() || seed.is_some() {
+                let seed = *seed.get_or_insert_with(rand::random);
+                frontend.say(&format!("🎲 Seed: {} (replay this quiz with --seed {})", seed, seed));
+                query = query.seed(seed);
             }
-            AnswerResult::Wrong => {
-                println!("❌ Wrong!");
-                println!("👉 Explanation: {}", quiz.explanation);
+            query.run(&all_quizzes)
+        } else {
+            // A client cannot answer the menu; it gets the random quiz.
+            match if jsonl { 1 } else { select_mode() } {
+                1 => {
+                    let seed = *seed.get_or_insert_with(rand::random);
+                    frontend.say(&format!("🎲 Seed: {} (replay this quiz with --seed {})", seed, seed));
+                    pick_random(&all_quizzes, 5, seed)
+                }
+                2 => all_quizzes,
+                _ => unreachable!(),
+            }
+        };
+
+        if quizzes.is_empty() {
+            fail("no questions match the given filters");
+        }
+
+        let mut state = QuizState::new();
+        if args.exam {
+            state = state.as_exam();
+        }
+        if args.partial_credit {
+            state = state.with_credit(Credit::Partial);
+        }
+
+        if args.shuffle_choices {
+            // The quiz seed also decides the choice order, so a replay matches.
+            state = state.with_shuffled_choices(seed.unwrap_or_else(rand::random));
+        }
+        let session = Session::new(&quizzes, &state, seed, now_millis());
+        (quizzes, state, session)
+    };
+
+    // A resumed quiz gets the limits of this run, counting from now.
+    state = state.with_time_limits(args.time_limit, args.total_time);
+
+    if args.exam {
+        run_exam(&quizzes, &mut state);
+    }
+
+    let total = adaptive.as_ref().map_or(quizzes.len(), |adaptive| adaptive.total(quizzes.len()));
+    let on_answer = |quiz: &quiz::Quiz, attempt: &Attempt, quizzes: &mut Vec<quiz::Quiz>| {
+        session.record(quiz.id, attempt.response.clone(), now_millis());
+        if let Some(path) = resume_path {
+            save_session(path, &session);
+        }
+        let adaptive = adaptive.as_mut()?;
+        let before = adaptive.level();
+        adaptive.after(attempt.result, quizzes).map(|level| {
+            if level > before {
+                format!("⬆️  Moving up to {}.", level)
+            } else {
+                format!("⬇️  Moving down to {}.", level)
             }
-            AnswerResult::Finished => break,
+        })
+    };
+    frontend.practice(&mut quizzes, &mut state, total, args.scoring, on_answer);
+
+    if let Some(path) = resume_path {
+        // Nothing left to resume; the next run starts a new quiz.
+        let _ = fs::remove_file(path);
+    }
+
+    log_history(&args.history.path, &state.history);
+    let report = state.report(&quizzes, args.scoring.strategy().as_ref());
+    let mut summary = format!(
+        "\n🎉 Quiz finished!\nScore: {}/{}\nPoints: {:.1}/{:.1} ({:.0}%, {} scoring)",
+        state.score,
+        quizzes.len(),
+        report.points,
+        report.max_points,
+        report.percent(),
+        args.scoring
+    );
+    if let Some(adaptive) = &adaptive {
+        summary += &format!("\n📶 Level reached: {} (highest: {})", adaptive.level(), adaptive.highest);
+    }
+    let verdict = (!args.pass_marks.is_empty()).then(|| Verdict::judge(&args.pass_marks, &quizzes, &state));
+    if let Some(verdict) = &verdict {
+        for check in &verdict.checks {
+            let icon = if check.passed { "✅" } else { "❌" };
+            summary += &format!("\n{} Pass mark {}: {}/{} correct", icon, check.mark, check.correct, check.total);
         }
+        summary += if verdict.passed { "\n🎓 Passed!" } else { "\n🚫 Not passed." };
     }
+    let status = match &verdict {
+        Some(verdict) if !verdict.passed => NOT_PASSED,
+        _ => 0,
+    };
+    // The quiz is over, so from here on the verdict decides.
+    LEAVE_STATUS.store(status, Ordering::Relaxed);
+    // A gate is judged on this one round and must not wait for input, so it
+    // offers no retries.
+    frontend.finish(quizzes, state, args.scoring, &summary, verdict.is_none());
 
-    println!("\n🎉 Quiz finished!");
-    println!("Score: {}/{}", state.score, quizzes.len());
+    if let Some(verdict) = verdict {
+        if jsonl {
+            jsonl::emit(&mut io::stdout(), &Event::Verdict(verdict)).unwrap_or_else(jsonl::abort);
+        } else {
+            // The last line of output, for scripts.
+            println!("{}", verdict.to_json());
+        }
+    }
+    if status != 0 {
+        process::exit(status);
+    }
 }
@@ -0,0 +1,726 @@
+use std::env;
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Cell, Clear, Gauge, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use rust_quiz::engine::{
+    describe_response, describe_solution, AnswerResult, Attempt, Phase, QuizState, Response, SystemClock,
+};
+use rust_quiz::highlight::{self, Kind};
+use rust_quiz::quiz::{Answer, Quiz};
+use rust_quiz::scoring::Scoring;
+
+/// How often the clock is redrawn while waiting for a key.
+const TICK: Duration = Duration::from_millis(250);
+/// Width of the score sidebar.
+const SIDEBAR: u16 = 28;
+
+/// Whether the full-screen interface can be shown: both ends must be a
+/// terminal, and not one that declares itself dumb.
+pub fn supported() -> bool {
+    io::stdin().is_terminal()
+        && io::stdout().is_terminal()
+        && env::var("TERM").map_or(true, |term| term != "dumb")
+}
+
+/// Gives the terminal back and ends the program with `err`.
+pub fn abort<T>(err: io::Error) -> T {
+    ratatui::restore();
+    crate::fail(&format!("terminal error: {}", err))
+}
+
+/// The terminal in full-screen mode, given back to the shell when dropped.
+pub struct Screen {
+    terminal: DefaultTerminal,
+}
+
+impl Screen {
+    pub fn enter() -> io::Result<Self> {
+        Ok(Self { terminal: ratatui::try_init()? })
+    }
+
+    /// Asks the questions one at a time, like the plain `run_practice`.
+    /// `total` is how long the quiz is expected to get, for the progress bar.
+    /// A note returned by `on_answer` is shown with the feedback.
+    pub fn practice(
+        &mut self,
+        quizzes: &mut Vec<Quiz>,
+        state: &mut QuizState,
+        total: usize,
+        scoring: Scoring,
+        mut on_answer: impl FnMut(&Quiz, &Attempt, &mut Vec<Quiz>) -> Option<String>,
+    ) -> io::Result<()> {
+        let mut note = None;
+        while let Some(quiz) = state.current_quiz(quizzes).cloned() {
+            if state.question_started_at.is_none() {
+                state.start_question(&SystemClock);
+            }
+            let order = state.choice_order(&quiz);
+            let mut cursor = Cursor::new(&quiz);
+
+            while state.phase() == Phase::Answering {
+                if state.check_time(quizzes, &SystemClock).is_some() {
+                    break;
+                }
+                let view = View { quizzes, state, quiz: &quiz, order: &order, cursor: &cursor, total, scoring, note: note.as_deref() };
+                self.terminal.draw(|frame| draw_question(frame, &view))?;
+                let Some(key) = next_key(TICK)? else {
+                    continue;
+                };
+                match on_key(key, &quiz, &order, state, &mut cursor) {
+                    Action::Submit => {
+                        state.submit_selection_at(quizzes, &SystemClock);
+                    }
+                    Action::Quit => leave(),
+                    Action::None => {}
+                }
+            }
+            // The whole quiz ran out of time.
+            if state.finished {
+                break;
+            }
+
+            note = state.history.last().and_then(|attempt| on_answer(&quiz, attempt, quizzes));
+            loop {
+                let view = View { quizzes, state, quiz: &quiz, order: &order, cursor: &cursor, total, scoring, note: note.as_deref() };
+                self.terminal.draw(|frame| {
+                    draw_question(frame, &view);
+                    draw_feedback(frame, &view);
+                })?;
+                match next_key(TICK)? {
+                    Some(key) if is_quit(&key) => leave(),
+                    Some(_) => break,
+                    None => {}
+                }
+            }
+            state.next(quizzes);
+        }
+        Ok(())
+    }
+
+    /// Shows every answer of a finished quiz in a table, with the details of
+    /// one on Enter. Returns whether the learner asked to retry the
+    /// questions they missed.
+    pub fn review(&mut self, quizzes: &[Quiz], state: &QuizState, scoring: Scoring) -> io::Result<bool> {
+        let missed = state.mistakes(quizzes).len();
+        let mut table = TableState::default().with_selected(Some(0));
+        let mut details = false;
+        loop {
+            self.terminal.draw(|frame| {
+                draw_review(frame, quizzes, state, scoring, missed, &mut table);
+                if details {
+                    draw_details(frame, quizzes, state, table.selected().unwrap_or(0));
+                }
+            })?;
+            let Some(key) = next_key(TICK)? else {
+                continue;
+            };
+            if details {
+                details = false;
+                continue;
+            }
+            match key.code {
+                _ if is_quit(&key) => return Ok(false),
+                KeyCode::Char('q') => return Ok(false),
+                KeyCode::Char('r') if missed > 0 => return Ok(true),
+                KeyCode::Up | KeyCode::Char('k') => table.select_previous(),
+                KeyCode::Down | KeyCode::Char('j')
+                    if table.selected().is_some_and(|i| i + 1 < state.history.len()) =>
+                {
+                    table.select_next()
+                }
+                KeyCode::Enter if !state.history.is_empty() => details = true,
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}
+
+/// Ends the program from the middle of a quiz, as running out of input does
+/// in the plain interface. A `--resume` file already holds the progress.
+fn leave() -> ! {
+    ratatui::restore();
+    println!("👋 Left the quiz.");
+    crate::exit_early();
+}
+
+/// The next key pressed within `timeout`, if any.
+fn next_key(timeout: Duration) -> io::Result<Option<KeyEvent>> {
+    if !event::poll(timeout)? {
+        return Ok(None);
+    }
+    match event::read()? {
+        Event::Key(key) if key.kind == KeyEventKind::Press => Ok(Some(key)),
+        _ => Ok(None),
+    }
+}
+
+fn is_quit(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Esc || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+/// Keyboard state for the question on screen.
+#[derive(Debug, Default)]
+struct Cursor {
+    /// Highlighted position among the displayed choices, or the blank being
+    /// typed into.
+    row: usize,
+    /// What has been typed: one entry for a typed answer, one per blank.
+    typed: Vec<String>,
+    /// First code line shown.
+    scroll: u16,
+}
+
+impl Cursor {
+    fn new(quiz: &Quiz) -> Self {
+        let fields = match &quiz.correct {
+            Answer::Text(_) => 1,
+            Answer::Blanks { .. } => quiz.blank_count(),
+            _ => 0,
+        };
+        Self {
+            typed: vec![String::new(); fields],
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    None,
+    Submit,
+    Quit,
+}
+
+/// Applies a key press to the pending answer.
+///
+/// Choices are picked with the arrow keys (or `j`/`k`) and Space, or by
+/// number; Enter picks and submits a single answer, and submits a complete
+/// "select all" or ordering answer. Typed answers are edited in place, Tab
+/// moving between blanks. Page Up and Page Down scroll the code.
+fn on_key(key: KeyEvent, quiz: &Quiz, order: &[usize], state: &mut QuizState, cursor: &mut Cursor) -> Action {
+    if is_quit(&key) {
+        return Action::Quit;
+    }
+    let code_lines = quiz.code.as_deref().map_or(0, |code| code.lines().count()) as u16;
+    match key.code {
+        KeyCode::PageDown => {
+            cursor.scroll = (cursor.scroll + 5).min(code_lines.saturating_sub(1));
+            return Action::None;
+        }
+        KeyCode::PageUp => {
+            cursor.scroll = cursor.scroll.saturating_sub(5);
+            return Action::None;
+        }
+        _ => {}
+    }
+
+    if !cursor.typed.is_empty() {
+        return on_typing_key(key, quiz, state, cursor);
+    }
+
+    let last = order.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => cursor.row = cursor.row.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => cursor.row = (cursor.row + 1).min(last),
+        KeyCode::Char(' ') => state.select_choice(quiz, order[cursor.row]),
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            let n = c.to_digit(10).unwrap_or(0) as usize;
+            if (1..=order.len()).contains(&n) {
+                cursor.row = n - 1;
+                state.select_choice(quiz, order[cursor.row]);
+            }
+        }
+        KeyCode::Backspace => {
+            if let Some(Response::Order(mut placed)) = state.selection.clone() {
+                placed.pop();
+                state.clear_selection();
+                if !placed.is_empty() {
+                    state.select(Response::Order(placed));
+                }
+            }
+        }
+        KeyCode::Enter => match &quiz.correct {
+            Answer::Single(_) => {
+                state.select_choice(quiz, order[cursor.row]);
+                return Action::Submit;
+            }
+            _ if state.can_submit(quiz) => return Action::Submit,
+            Answer::Order { .. } => {
+                state.select_choice(quiz, order[cursor.row]);
+                // Jump to the next line still to be placed.
+                if let Some(next) = (0..order.len()).find(|&n| !state.is_selected(order[n])) {
+                    cursor.row = next;
+                }
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+    Action::None
+}
+
+fn on_typing_key(key: KeyEvent, quiz: &Quiz, state: &mut QuizState, cursor: &mut Cursor) -> Action {
+    let fields = cursor.typed.len();
+    match key.code {
+        KeyCode::Tab | KeyCode::Down => cursor.row = (cursor.row + 1) % fields,
+        KeyCode::BackTab | KeyCode::Up => cursor.row = (cursor.row + fields - 1) % fields,
+        KeyCode::Enter if state.can_submit(quiz) => return Action::Submit,
+        KeyCode::Enter => cursor.row = (cursor.row + 1) % fields,
+        KeyCode::Backspace => {
+            cursor.typed[cursor.row].pop();
+        }
+        KeyCode::Char(c) => cursor.typed[cursor.row].push(c),
+        _ => return Action::None,
+    }
+
+    let text = cursor.typed[cursor.row].trim().to_string();
+    match &quiz.correct {
+        Answer::Blanks { .. } => state.fill_blank(quiz, cursor.row, text),
+        _ if text.is_empty() => state.clear_selection(),
+        _ => state.select(Response::Text(text)),
+    }
+    Action::None
+}
+
+/// Everything drawn for the question on screen.
+struct View<'a> {
+    quizzes: &'a [Quiz],
+    state: &'a QuizState,
+    quiz: &'a Quiz,
+    order: &'a [usize],
+    cursor: &'a Cursor,
+    total: usize,
+    scoring: Scoring,
+    note: Option<&'a str>,
+}
+
+/// Lines `text` takes up when wrapped to `width`, roughly: words pushed to
+/// the next line are not accounted for.
+fn wrapped_height(text: &str, width: u16) -> u16 {
+    let width = width.max(1) as usize;
+    text.lines().map(|line| line.chars().count().max(1).div_ceil(width) as u16).sum()
+}
+
+fn draw_question(frame: &mut Frame, view: &View) {
+    let [main, progress, help] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(3), Constraint::Length(1)]).areas(frame.area());
+    let [left, sidebar] = Layout::horizontal([Constraint::Min(0), Constraint::Length(SIDEBAR)]).areas(main);
+
+    let quiz = view.quiz;
+    let question_height = wrapped_height(&quiz.question, left.width.saturating_sub(2)) + 2;
+    let answer_height = if view.cursor.typed.is_empty() { view.order.len() } else { view.cursor.typed.len() } as u16 + 2;
+    let code_height = if quiz.code.is_some() { Constraint::Min(3) } else { Constraint::Length(0) };
+    let [question, code, answers] = Layout::vertical([
+        Constraint::Length(question_height),
+        code_height,
+        Constraint::Length(answer_height),
+    ])
+    .areas(left);
+
+    frame.render_widget(
+        Paragraph::new(quiz.question.as_ref())
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(format!(" [{}] {} ", quiz.id, quiz.title)).bold()),
+        question,
+    );
+    if let Some(source) = &quiz.code {
+        frame.render_widget(
+            Paragraph::new(code_lines(source))
+                .scroll((view.cursor.scroll, 0))
+                .block(Block::bordered().title(" Code (PgUp/PgDn) ")),
+            code,
+        );
+    }
+    frame.render_widget(answer_list(view).block(Block::bordered().title(" Answer ")), answers);
+    frame.render_widget(sidebar_text(view).block(Block::bordered().title(" Score ")), sidebar);
+
+    let (done, _) = view.state.progress(view.quizzes.len());
+    let total = view.total.max(view.quizzes.len()).max(1);
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::bordered().title(" Progress "))
+            .gauge_style(Style::default().fg(Color::Cyan))
+            .ratio(done as f64 / total as f64)
+            .label(format!("{}/{}", done, total)),
+        progress,
+    );
+    frame.render_widget(Line::from(help_text(quiz)).dim(), help);
+}
+
+/// The color of a token of this kind.
+fn token_style(kind: Kind) -> Style {
+    let style = Style::default();
+    match kind {
+        Kind::Plain => style,
+        Kind::Keyword => style.fg(Color::Magenta),
+        Kind::Type | Kind::Number => style.fg(Color::Yellow),
+        Kind::Function => style.fg(Color::Blue),
+        Kind::Macro | Kind::Lifetime => style.fg(Color::Cyan),
+        Kind::String => style.fg(Color::Green),
+        Kind::Comment | Kind::Attribute => style.fg(Color::DarkGray),
+        Kind::Blank => style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+    }
+}
+
+/// `code` highlighted, with line numbers.
+fn code_lines(code: &str) -> Vec<Line<'_>> {
+    let lines = highlight::lines(code);
+    let width = lines.len().to_string().len();
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(n, tokens)| {
+            let gutter = Span::styled(format!("{:>width$} │ ", n + 1), Style::default().fg(Color::DarkGray));
+            let spans = tokens.into_iter().map(|token| Span::styled(token.text, token_style(token.kind)));
+            Line::from_iter(std::iter::once(gutter).chain(spans))
+        })
+        .collect()
+}
+
+fn help_text(quiz: &Quiz) -> &'static str {
+    match &quiz.correct {
+        Answer::Single(_) => " ↑↓ move · Enter answer · 1-9 pick · Esc quit",
+        Answer::Multiple(_) => " ↑↓ move · Space toggle · Enter submit · Esc quit",
+        Answer::Order { .. } => " ↑↓ move · Enter place line · Backspace undo · Enter submit when all placed · Esc quit",
+        Answer::Text(_) => " Type your answer · Enter submit · Esc quit",
+        Answer::Blanks { .. } => " Type each blank · Tab next blank · Enter submit · Esc quit",
+    }
+}
+
+fn answer_list<'a>(view: &View<'a>) -> Paragraph<'a> {
+    let state = view.state;
+    let highlight = Style::default().add_modifier(Modifier::REVERSED);
+
+    if !view.cursor.typed.is_empty() {
+        let lines: Vec<Line> = view
+            .cursor
+            .typed
+            .iter()
+            .enumerate()
+            .map(|(i, typed)| {
+                let label = if view.cursor.typed.len() == 1 { "Answer".to_string() } else { format!("Blank {}", i + 1) };
+                let line = Line::from(vec![Span::raw(format!("{}: ", label)), Span::raw(typed.clone()), Span::raw("▏")]);
+                if i == view.cursor.row { line.style(highlight) } else { line }
+            })
+            .collect();
+        return Paragraph::new(lines);
+    }
+
+    let placed = match &state.selection {
+        Some(Response::Order(placed)) => placed.clone(),
+        _ => Vec::new(),
+    };
+    let lines: Vec<Line> = view
+        .order
+        .iter()
+        .enumerate()
+        .map(|(n, &i)| {
+            let selected = state.is_selected(i);
+            let mark = match &view.quiz.correct {
+                Answer::Multiple(_) => if selected { "[x]".to_string() } else { "[ ]".to_string() },
+                Answer::Order { .. } => match placed.iter().position(|&p| p == i) {
+                    Some(at) => format!("[{}]", at + 1),
+                    None => "[ ]".to_string(),
+                },
+                _ => if selected { "(•)".to_string() } else { "( )".to_string() },
+            };
+            let pointer = if n == view.cursor.row { "▶ " } else { "  " };
+            let line = Line::from(format!("{}{} {}. {}", pointer, mark, n + 1, view.quiz.choices[i]));
+            if n == view.cursor.row { line.style(highlight) } else { line }
+        })
+        .collect();
+    Paragraph::new(lines)
+}
+
+fn sidebar_text<'a>(view: &View<'a>) -> Paragraph<'a> {
+    let state = view.state;
+    let report = state.report(view.quizzes, view.scoring.strategy().as_ref());
+    let streak = state.history.iter().rev().take_while(|a| a.result == AnswerResult::Correct).count();
+
+    let mut lines = vec![
+        Line::from(format!("Score   {}/{}", state.score, state.history.len())),
+        Line::from(format!("Points  {:.1}/{:.1}", report.points, report.max_points)),
+        Line::from(format!("        ({})", view.scoring)).dim(),
+        Line::from(format!("Streak  {}", streak)),
+    ];
+    if let Some(left) = state.time_left(&SystemClock) {
+        let style = if left.as_secs() < 10 { Style::default().fg(Color::Red) } else { Style::default() };
+        lines.push(Line::styled(format!("Time    ⏱  {}s", left.as_secs()), style));
+    }
+    if let Some(note) = view.note {
+        lines.push(Line::default());
+        lines.push(Line::from(note.to_string()));
+    }
+    Paragraph::new(lines).wrap(Wrap { trim: false })
+}
+
+/// The result of the question just answered, over the question itself.
+fn draw_feedback(frame: &mut Frame, view: &View) {
+    let Some(feedback) = &view.state.feedback else {
+        return;
+    };
+    let quiz = view.quiz;
+    let (title, color) = match feedback.result {
+        _ if feedback.timed_out => (" ⏰ Time's up! ", Color::Red),
+        AnswerResult::Correct => (" ✅ Correct! ", Color::Green),
+        AnswerResult::Partial => (" 🟡 Partially correct ", Color::Yellow),
+        _ => (" ❌ Wrong ", Color::Red),
+    };
+
+    let mut text = Text::default();
+    if !feedback.timed_out {
+        push_labeled(&mut text, "Your answer:", &describe_response(quiz, &feedback.response));
+    }
+    if feedback.result != AnswerResult::Correct {
+        push_labeled(&mut text, "Correct answer:", &describe_solution(quiz));
+    }
+    text.push_line(Line::default());
+    text.push_line(Line::from(format!("👉 {}", quiz.explanation)));
+    if let Some(note) = view.note {
+        text.push_line(Line::default());
+        text.push_line(Line::from(note.to_string()));
+    }
+    text.push_line(Line::default());
+    text.push_line(Line::from("Press any key to continue").dim());
+
+    popup(frame, title, color, text);
+}
+
+/// Adds `text` after `label`, putting multi-line answers on their own
+/// indented lines.
+fn push_labeled(text: &mut Text, label: &str, value: &str) {
+    if value.contains('\n') {
+        text.push_line(Line::from(label.to_string()).bold());
+        for line in value.lines() {
+            text.push_line(Line::from(format!("   {}", line)));
+        }
+    } else {
+        text.push_line(Line::from(vec![Span::from(label.to_string()).bold(), Span::raw(format!(" {}", value))]));
+    }
+}
+
+fn popup(frame: &mut Frame, title: &str, color: Color, text: Text) {
+    let area = frame.area().centered(Constraint::Percentage(70), Constraint::Percentage(60));
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(title.to_string()).border_style(Style::default().fg(color))),
+        area,
+    );
+}
+
+fn draw_review(
+    frame: &mut Frame,
+    quizzes: &[Quiz],
+    state: &QuizState,
+    scoring: Scoring,
+    missed: usize,
+    table: &mut TableState,
+) {
+    let [summary, rows, help] =
+        Layout::vertical([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+    let report = state.report(quizzes, scoring.strategy().as_ref());
+    frame.render_widget(
+        Paragraph::new(format!(
+            "Score {}/{} · Points {:.1}/{:.1} ({:.0}%, {} scoring)",
+            state.score,
+            quizzes.len(),
+            report.points,
+            report.max_points,
+            report.percent(),
+            scoring
+        ))
+        .block(Block::bordered().title(" 🎉 Quiz finished ").bold()),
+        summary,
+    );
+
+    let body: Vec<Row> = state
+        .history
+        .iter()
+        .map(|attempt| {
+            let quiz = quizzes.iter().find(|q| q.id == attempt.quiz_id);
+            let (mark, color) = match attempt.result {
+                AnswerResult::Correct => ("✅", Color::Green),
+                AnswerResult::Partial => ("🟡", Color::Yellow),
+                _ => ("❌", Color::Red),
+            };
+            let answer = match quiz {
+                _ if attempt.timed_out => "⏰ out of time".to_string(),
+                Some(quiz) => describe_response(quiz, &attempt.response).replace('\n', " / "),
+                None => String::new(),
+            };
+            let time = attempt.time_taken.map_or(String::new(), |ms| format!("{:.1}s", ms as f64 / 1000.0));
+            Row::new(vec![
+                Cell::from(mark),
+                Cell::from(attempt.quiz_id.to_string()),
+                Cell::from(quiz.map_or(String::new(), |q| q.title.to_string())),
+                Cell::from(answer).fg(color),
+                Cell::from(time),
+            ])
+        })
+        .collect();
+    let widths = [
+        Constraint::Length(3),
+        Constraint::Length(5),
+        Constraint::Percentage(45),
+        Constraint::Percentage(40),
+        Constraint::Length(7),
+    ];
+    frame.render_stateful_widget(
+        Table::new(body, widths)
+            .header(Row::new(vec!["", "Id", "Question", "Your answer", "Time"]).bold())
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("▶ ")
+            .block(Block::bordered().title(" Review ")),
+        rows,
+        table,
+    );
+
+    let retry = if missed > 0 { format!(" · r retry {} missed", missed) } else { String::new() };
+    frame.render_widget(Line::from(format!(" ↑↓ move · Enter details{} · q done", retry)).dim(), help);
+}
+
+/// The question behind row `row` of the review, with its answers and
+/// explanation.
+fn draw_details(frame: &mut Frame, quizzes: &[Quiz], state: &QuizState, row: usize) {
+    let Some(attempt) = state.history.get(row) else {
+        return;
+    };
+    let Some(quiz) = quizzes.iter().find(|q| q.id == attempt.quiz_id) else {
+        return;
+    };
+
+    let mut text = Text::from(quiz.question.to_string());
+    text.push_line(Line::default());
+    if attempt.timed_out {
+        text.push_line(Line::from("⏰ Ran out of time"));
+    } else {
+        push_labeled(&mut text, "Your answer:", &describe_response(quiz, &attempt.response));
+    }
+    push_labeled(&mut text, "Correct answer:", &describe_solution(quiz));
+    text.push_line(Line::default());
+    text.push_line(Line::from(format!("👉 {}", quiz.explanation)));
+
+    popup(frame, &format!(" [{}] {} ", quiz.id, quiz.title), Color::Cyan, text);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use rust_quiz::quiz::quiz_bank;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn first(matches: impl Fn(&Answer) -> bool) -> Quiz {
+        quiz_bank().into_iter().find(|q| matches(&q.correct)).expect("the bank has such a question")
+    }
+
+    #[test]
+    fn test_arrow_keys_pick_a_single_answer() {
+        let quiz = first(|a| matches!(a, Answer::Single(_)));
+        let order: Vec<usize> = (0..quiz.choices.len()).rev().collect();
+        let mut state = QuizState::new();
+        let mut cursor = Cursor::new(&quiz);
+
+        assert_eq!(on_key(press(KeyCode::Down), &quiz, &order, &mut state, &mut cursor), Action::None);
+        assert_eq!(on_key(press(KeyCode::Enter), &quiz, &order, &mut state, &mut cursor), Action::Submit);
+        assert_eq!(state.selection, Some(Response::Choice(order[1])));
+    }
+
+    #[test]
+    fn test_enter_waits_for_a_complete_answer() {
+        let quiz = first(|a| matches!(a, Answer::Multiple(_)));
+        let order: Vec<usize> = (0..quiz.choices.len()).collect();
+        let mut state = QuizState::new();
+        let mut cursor = Cursor::new(&quiz);
+
+        assert_eq!(on_key(press(KeyCode::Enter), &quiz, &order, &mut state, &mut cursor), Action::None);
+        on_key(press(KeyCode::Char(' ')), &quiz, &order, &mut state, &mut cursor);
+        on_key(press(KeyCode::Char('2')), &quiz, &order, &mut state, &mut cursor);
+        assert_eq!(state.selection, Some(Response::Choices(vec![0, 1])));
+        assert_eq!(on_key(press(KeyCode::Enter), &quiz, &order, &mut state, &mut cursor), Action::Submit);
+    }
+
+    #[test]
+    fn test_typing_fills_blanks() {
+        let quiz = first(|a| matches!(a, Answer::Blanks { .. }));
+        let order: Vec<usize> = Vec::new();
+        let mut state = QuizState::new();
+        let mut cursor = Cursor::new(&quiz);
+
+        for c in "mut".chars() {
+            on_key(press(KeyCode::Char(c)), &quiz, &order, &mut state, &mut cursor);
+        }
+        on_key(press(KeyCode::Tab), &quiz, &order, &mut state, &mut cursor);
+
+        assert_eq!(cursor.row, 1 % quiz.blank_count());
+        assert_eq!(cursor.typed[0], "mut");
+        let Some(Response::Blanks(filled)) = &state.selection else { panic!("expected blanks") };
+        assert_eq!(filled[0], "mut");
+    }
+
+    #[test]
+    fn test_escape_quits() {
+        let quiz = first(|a| matches!(a, Answer::Single(_)));
+        let mut cursor = Cursor::new(&quiz);
+
+        assert_eq!(on_key(press(KeyCode::Esc), &quiz, &[0], &mut QuizState::new(), &mut cursor), Action::Quit);
+    }
+
+    #[test]
+    fn test_code_is_numbered_and_highlighted() {
+        let lines = code_lines("fn main() {\n    let x = 1;\n}");
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].spans[0].content, "2 │ ");
+        let keyword = &lines[1].spans[2];
+        assert_eq!(keyword.content, "let");
+        assert_eq!(keyword.style.fg, Some(Color::Magenta));
+    }
+
+    #[test]
+    fn test_draws_question_and_sidebar() {
+        let quizzes = quiz_bank();
+        let quiz = &quizzes[0];
+        let state = QuizState::new();
+        let order = state.choice_order(quiz);
+        let cursor = Cursor::new(quiz);
+        let view = View {
+            quizzes: &quizzes,
+            state: &state,
+            quiz,
+            order: &order,
+            cursor: &cursor,
+            total: quizzes.len(),
+            scoring: Scoring::Plain,
+            note: None,
+        };
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| draw_question(frame, &view)).unwrap();
+
+        let screen: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains(quiz.title.as_ref() as &str));
+        assert!(screen.contains(quiz.choices[0].as_ref() as &str));
+        assert!(screen.contains("Score"));
+        assert!(screen.contains(&format!("0/{}", quizzes.len())));
+    }
+}
@@ -0,0 +1,55 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Plays questions 1 and 2 with `answers` piped in, the first answer right
+/// and the second wrong, and the history kept out of the working directory.
+fn play(pass_mark: &str, answers: &str) -> Output {
+    let history = std::env::temp_dir().join(format!("rust_quiz_pass_mark_{}_{}.json", std::process::id(), pass_mark));
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cli"))
+        .args(["--ids", "1,2", "--pass-mark", pass_mark, "--history"])
+        .arg(&history)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(answers.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    let _ = std::fs::remove_file(history);
+    output
+}
+
+fn verdicts(output: &Output) -> Vec<String> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.starts_with(r#"{"passed":"#))
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn test_passing_mark_exits_cleanly_with_a_verdict() {
+    let output = play("1", "4\n1\n");
+
+    assert_eq!(output.status.code(), Some(0));
+    let verdicts = verdicts(&output);
+    assert_eq!(verdicts.len(), 1);
+    assert!(verdicts[0].starts_with(r#"{"passed":true,"score":1,"total":2"#));
+    assert!(String::from_utf8_lossy(&output.stdout).trim_end().ends_with(&verdicts[0]));
+}
+
+#[test]
+fn test_missed_mark_exits_with_its_own_status() {
+    let output = play("100%", "4\n1\n");
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(verdicts(&output).len(), 1);
+}
+
+#[test]
+fn test_leaving_early_does_not_pass() {
+    let output = play("1", "4\n");
+
+    assert_eq!(output.status.code(), Some(3));
+    assert!(verdicts(&output).is_empty());
+}
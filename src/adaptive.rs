@@ -0,0 +1,223 @@
+use crate::engine::AnswerResult;
+use crate::quiz::{Level, Quiz};
+use crate::random::Rng;
+
+/// Picks questions one at a time, moving between level tiers as the learner
+/// answers.
+///
+/// A run of `promote_after` correct answers moves one tier up and a run of
+/// `demote_after` misses one tier down; partial credit counts as neither and
+/// breaks both runs. Only tiers that have questions in the pool are used.
+/// Questions are drawn from the current tier, or the nearest one with
+/// questions left, and never repeat.
+///
+/// The played list grows as the quiz goes: start it with [`Adaptive::first`]
+/// and call [`Adaptive::after`] with each result before moving on with
+/// [`QuizState::next`](crate::engine::QuizState::next).
+#[derive(Clone, Debug)]
+pub struct Adaptive {
+    pool: Vec<Quiz>,
+    levels: Vec<Level>,
+    tier: usize,
+    correct_run: usize,
+    miss_run: usize,
+    pub highest: Level,
+    pub promote_after: usize,
+    pub demote_after: usize,
+    /// How many questions to ask in total.
+    pub length: usize,
+}
+
+impl Adaptive {
+    /// Starts at `start`, or the nearest tier the pool has questions for.
+    /// Without [`Adaptive::seed`] questions come in pool order.
+    pub fn new(pool: Vec<Quiz>, start: Level) -> Self {
+        let mut levels: Vec<Level> = pool.iter().map(|q| q.level).collect();
+        levels.sort();
+        levels.dedup();
+        let tier = nearest(&levels, start).unwrap_or(0);
+        Self {
+            highest: levels.get(tier).copied().unwrap_or(start),
+            pool,
+            levels,
+            tier,
+            correct_run: 0,
+            miss_run: 0,
+            promote_after: 3,
+            demote_after: 2,
+            length: 10,
+        }
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        Rng::new(seed).shuffle(&mut self.pool);
+        self
+    }
+
+    pub fn promote_after(mut self, answers: usize) -> Self {
+        self.promote_after = answers.max(1);
+        self
+    }
+
+    pub fn demote_after(mut self, answers: usize) -> Self {
+        self.demote_after = answers.max(1);
+        self
+    }
+
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// The tier questions are currently drawn from.
+    pub fn level(&self) -> Level {
+        self.levels.get(self.tier).copied().unwrap_or(self.highest)
+    }
+
+    /// How long the quiz will be once `played` questions have been asked:
+    /// `length`, or fewer if the pool runs out first.
+    pub fn total(&self, played: usize) -> usize {
+        self.length.min(played + self.pool.len())
+    }
+
+    /// The opening question list: one question, or none if the pool is empty.
+    pub fn first(&mut self) -> Vec<Quiz> {
+        if self.length == 0 {
+            return Vec::new();
+        }
+        self.draw().into_iter().collect()
+    }
+
+    /// Moves between tiers after `result` and adds the next question to
+    /// `played`, unless the quiz is long enough or the pool is used up.
+    /// Returns the new level if it changed.
+    pub fn after(&mut self, result: AnswerResult, played: &mut Vec<Quiz>) -> Option<Level> {
+        let before = self.level();
+        self.record(result);
+        if played.len() < self.length
+            && let Some(quiz) = self.draw()
+        {
+            played.push(quiz);
+        }
+        let level = self.level();
+        (level != before).then_some(level)
+    }
+
+    fn record(&mut self, result: AnswerResult) {
+        match result {
+            AnswerResult::Correct => {
+                self.miss_run = 0;
+                self.correct_run += 1;
+                if self.correct_run >= self.promote_after && self.tier + 1 < self.levels.len() {
+                    self.tier += 1;
+                    self.correct_run = 0;
+                }
+            }
+            AnswerResult::Wrong => {
+                self.correct_run = 0;
+                self.miss_run += 1;
+                if self.miss_run >= self.demote_after && self.tier > 0 {
+                    self.tier -= 1;
+                    self.miss_run = 0;
+                }
+            }
+            AnswerResult::Partial => {
+                self.correct_run = 0;
+                self.miss_run = 0;
+            }
+            AnswerResult::Finished => {}
+        }
+        self.highest = self.highest.max(self.level());
+    }
+
+    /// Takes the next question from the current tier, or the nearest tier
+    /// with questions left, preferring the easier one on a tie.
+    fn draw(&mut self) -> Option<Quiz> {
+        let left: Vec<Level> = self
+            .levels
+            .iter()
+            .copied()
+            .filter(|level| self.pool.iter().any(|q| q.level == *level))
+            .collect();
+        let level = left[nearest(&left, self.level())?];
+        let index = self.pool.iter().position(|q| q.level == level)?;
+        Some(self.pool.remove(index))
+    }
+}
+
+/// Index of the level in `levels` (sorted) closest to `wanted`, taking the
+/// lower one on a tie.
+fn nearest(levels: &[Level], wanted: Level) -> Option<usize> {
+    let rank = |level: Level| Level::ALL.iter().position(|l| *l == level).unwrap_or(0) as isize;
+    (0..levels.len()).min_by_key(|&i| (rank(levels[i]) - rank(wanted)).abs())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::quiz_bank;
+
+    fn play(adaptive: &mut Adaptive, results: &[AnswerResult]) -> Vec<Quiz> {
+        let mut played = adaptive.first();
+        for result in results {
+            adaptive.after(*result, &mut played);
+        }
+        played
+    }
+
+    #[test]
+    fn test_promotes_after_correct_run() {
+        let mut adaptive = Adaptive::new(quiz_bank(), Level::Intro).promote_after(2);
+        let played = play(&mut adaptive, &[AnswerResult::Correct, AnswerResult::Correct]);
+
+        assert_eq!(played[0].level, Level::Intro);
+        assert_eq!(played[1].level, Level::Intro);
+        assert_eq!(played[2].level, Level::Beginner);
+        assert_eq!(adaptive.level(), Level::Beginner);
+    }
+
+    #[test]
+    fn test_demotes_after_misses() {
+        let mut adaptive = Adaptive::new(quiz_bank(), Level::Intermediate);
+        let results = [AnswerResult::Wrong, AnswerResult::Partial, AnswerResult::Wrong, AnswerResult::Wrong];
+        let played = play(&mut adaptive, &results);
+
+        assert_eq!(played[3].level, Level::Intermediate);
+        assert_eq!(played[4].level, Level::BeginnerPlus);
+        assert_eq!(adaptive.highest, Level::Intermediate);
+    }
+
+    #[test]
+    fn test_falls_back_to_nearest_tier_without_repeats() {
+        // The bank has only three Intro questions.
+        let mut adaptive = Adaptive::new(quiz_bank(), Level::Intro).demote_after(1).length(6);
+        let played = play(&mut adaptive, &[AnswerResult::Wrong; 6]);
+
+        let levels: Vec<Level> = played.iter().map(|q| q.level).collect();
+        assert_eq!(levels[..3], [Level::Intro; 3]);
+        assert_eq!(levels[3..], [Level::Beginner; 3]);
+        let mut ids: Vec<u32> = played.iter().map(|q| q.id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 6);
+    }
+
+    #[test]
+    fn test_stops_at_length() {
+        let mut adaptive = Adaptive::new(quiz_bank(), Level::Beginner).length(2);
+        let played = play(&mut adaptive, &[AnswerResult::Correct; 3]);
+
+        assert_eq!(played.len(), 2);
+        assert_eq!(adaptive.total(played.len()), 2);
+    }
+
+    #[test]
+    fn test_start_snaps_to_a_tier_in_the_pool() {
+        let pool: Vec<Quiz> = quiz_bank().into_iter().filter(|q| q.level >= Level::BeginnerPlus).collect();
+        let adaptive = Adaptive::new(pool, Level::Intro);
+
+        assert_eq!(adaptive.level(), Level::BeginnerPlus);
+        assert_eq!(adaptive.total(0), 10);
+    }
+}
@@ -1,30 +1,366 @@
 #![allow(dead_code)]
 
-use crate::quiz::Quiz;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-#[derive(Debug)]
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::quiz::{Answer, Matching, Quiz, TextAnswer};
+use crate::random::Rng;
+
+#[derive(Clone, Debug)]
 pub struct QuizState {
     pub current: usize,
+    /// Number of questions answered fully correctly.
     pub score: usize,
+    /// Sum of the credit earned per question, between 0 and 1 each.
+    pub points: f64,
+    pub credit: Credit,
+    /// Seed for shuffling each question's choices; `None` keeps the
+    /// authored order.
+    pub choice_seed: Option<u64>,
+    /// The answer being built for the current question, not yet submitted.
+    pub selection: Option<Response>,
+    /// Outcome of the current question once it has been submitted.
+    pub feedback: Option<Feedback>,
+    /// Every submitted answer, oldest first.
+    pub history: Vec<Attempt>,
+    /// When the current question was shown, in milliseconds, if the front
+    /// end reports it through [`QuizState::start_question`].
+    pub question_started_at: Option<u64>,
+    /// When the first question was shown, in milliseconds.
+    pub started_at: Option<u64>,
+    /// Time allowed per question. Not used in exam mode.
+    pub question_limit: Option<Duration>,
+    /// Time allowed for the whole quiz.
+    pub total_limit: Option<Duration>,
+    /// Exam mode: answers are kept as drafts that can be revisited and are
+    /// only graded by [`QuizState::submit_exam`].
+    pub exam: bool,
+    /// Exam answers saved for questions other than the current one, by
+    /// question position.
+    pub drafts: BTreeMap<usize, Response>,
+    /// Question positions marked for review.
+    pub flagged: BTreeSet<usize>,
     pub finished: bool,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// One submitted answer, kept for the end-of-quiz review.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub quiz_id: u32,
+    pub response: Response,
+    pub result: AnswerResult,
+    pub earned: f64,
+    /// Milliseconds from showing the question to submitting it, when both
+    /// times are known.
+    pub time_taken: Option<u64>,
+    /// The time limit ran out before an answer was submitted.
+    pub timed_out: bool,
+}
+
+/// Which time limit ran out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timeout {
+    Question,
+    Total,
+}
+
+/// Where the engine gets the current time from, in milliseconds since the
+/// Unix epoch. Any `Fn() -> u64` closure is a clock, which keeps tests and
+/// the browser (where `SystemTime` is unavailable) simple.
+pub trait Clock {
+    fn now_millis(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn now_millis(&self) -> u64 {
+        self()
+    }
+}
+
+/// The operating system's wall clock.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+/// Where the current question is in its answer, submit, next cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Answering,
+    /// Submitted and showing feedback, waiting for [`QuizState::next`].
+    Submitted,
+    Finished,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Feedback {
+    pub result: AnswerResult,
+    /// Credit earned, from 0.0 to 1.0.
+    pub earned: f64,
+    pub response: Response,
+    pub timed_out: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AnswerResult {
     Correct,
+    /// Some credit was earned on a "select all that apply" question.
+    Partial,
     Wrong,
     Finished,
 }
 
+/// What the learner submitted for a question. Choice indices always refer
+/// to the authored order of `Quiz::choices`, even when they are shown
+/// shuffled.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+    Choice(usize),
+    Choices(Vec<usize>),
+    Text(String),
+    /// Choice indices in the order the learner arranged them.
+    Order(Vec<usize>),
+    /// One entry per blank, left to right.
+    Blanks(Vec<String>),
+}
+
+/// How questions with several correct choices are scored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Credit {
+    /// Full credit for exactly the correct set, nothing otherwise.
+    #[default]
+    Exact,
+    /// Each correct choice earns its share, each wrong choice takes one
+    /// share back, never going below zero.
+    Partial,
+}
+
+/// The order to show `quiz.choices` in: entry `n` is the authored index of
+/// the choice displayed in position `n`. Typed and fill-in-the-blank
+/// questions have nothing to shuffle.
+pub fn choice_order(quiz: &Quiz, seed: Option<u64>) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..quiz.choices.len()).collect();
+    if let Some(seed) = seed
+        && !matches!(quiz.correct, Answer::Text(_) | Answer::Blanks { .. })
+    {
+        // Mix in the id so questions sharing a seed get different orders.
+        let mixed = seed ^ u64::from(quiz.id).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        Rng::new(mixed).shuffle(&mut order);
+    }
+    order
+}
+
+/// Credit earned by `response`, from 0.0 (wrong) to 1.0 (correct).
+pub fn grade(quiz: &Quiz, response: &Response, credit: Credit) -> f64 {
+    match (&quiz.correct, response) {
+        (Answer::Single(_) | Answer::Multiple(_), Response::Choice(_) | Response::Choices(_)) => {
+            grade_choices(&quiz.correct, response, credit)
+        }
+        (Answer::Text(text), Response::Text(input)) if text_matches(text, input) => 1.0,
+        (Answer::Order { order }, Response::Order(given)) => grade_order(quiz, order, given, credit),
+        (Answer::Blanks { blanks }, Response::Blanks(given)) => {
+            let matched = blanks
+                .iter()
+                .zip(given)
+                .filter(|(blank, input)| text_matches(blank, input))
+                .count();
+            share(matched, blanks.len(), credit)
+        }
+        _ => 0.0,
+    }
+}
+
+/// `hits` out of `total` as full credit, or as a fraction with partial credit.
+fn share(hits: usize, total: usize, credit: Credit) -> f64 {
+    if total > 0 && hits == total {
+        1.0
+    } else if credit == Credit::Exact || total == 0 {
+        0.0
+    } else {
+        hits as f64 / total as f64
+    }
+}
+
+fn grade_choices(answer: &Answer, response: &Response, credit: Credit) -> f64 {
+    let mut chosen = match response {
+        Response::Choice(choice) => vec![*choice],
+        Response::Choices(choices) => choices.clone(),
+        _ => return 0.0,
+    };
+    chosen.sort_unstable();
+    chosen.dedup();
+
+    let mut expected = answer.correct_choices().to_vec();
+    expected.sort_unstable();
+    expected.dedup();
+
+    if chosen == expected {
+        return 1.0;
+    }
+
+    let hits = chosen.iter().filter(|c| expected.contains(c)).count();
+    let misses = chosen.len() - hits;
+    share(hits.saturating_sub(misses), expected.len(), credit)
+}
+
+/// Lines are compared by text, so identical lines such as `}` may be placed
+/// in either spot. Partial credit counts lines in the right position.
+fn grade_order(quiz: &Quiz, order: &[usize], given: &[usize], credit: Credit) -> f64 {
+    if given.len() != order.len() {
+        return 0.0;
+    }
+    let line = |i: &usize| quiz.choices.get(*i);
+    let placed = order
+        .iter()
+        .zip(given)
+        .filter(|(expected, given)| line(expected).is_some() && line(expected) == line(given))
+        .count();
+    share(placed, order.len(), credit)
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether typed `input` matches any of the accepted answers.
+pub fn text_matches(answer: &TextAnswer, input: &str) -> bool {
+    let input = input.trim();
+    answer.accepted.iter().any(|accepted| match answer.matching {
+        Matching::Exact => input == accepted.trim(),
+        Matching::Normalized => normalize_whitespace(input) == normalize_whitespace(accepted),
+        Matching::CaseInsensitive => {
+            normalize_whitespace(input).to_lowercase() == normalize_whitespace(accepted).to_lowercase()
+        }
+        Matching::Regex => Regex::new(&format!("^(?:{})$", accepted))
+            .map(|re| re.is_match(input))
+            .unwrap_or(false),
+    })
+}
+
+/// The correct answer to `quiz` in words, for feedback and reviews. Typed
+/// answers show the first accepted answer.
+pub fn describe_solution(quiz: &Quiz) -> String {
+    let first = |text: &TextAnswer| text.accepted.first().map(|a| a.to_string()).unwrap_or_default();
+    match &quiz.correct {
+        Answer::Single(_) | Answer::Multiple(_) => choice_texts(quiz, quiz.correct.correct_choices()),
+        Answer::Text(text) => first(text),
+        Answer::Order { order } => line_texts(quiz, order),
+        Answer::Blanks { blanks } => blanks.iter().map(first).collect::<Vec<_>>().join(", "),
+    }
+}
+
+/// What the learner answered, in the same form as [`describe_solution`].
+pub fn describe_response(quiz: &Quiz, response: &Response) -> String {
+    let described = match response {
+        Response::Choice(choice) => choice_texts(quiz, std::slice::from_ref(choice)),
+        Response::Choices(choices) => choice_texts(quiz, choices),
+        Response::Text(text) => text.trim().to_string(),
+        Response::Order(order) => line_texts(quiz, order),
+        Response::Blanks(blanks) => blanks.iter().map(|b| b.trim()).collect::<Vec<_>>().join(", "),
+    };
+    if described.trim().is_empty() {
+        "(no answer)".to_string()
+    } else {
+        described
+    }
+}
+
+fn choice_texts(quiz: &Quiz, indices: &[usize]) -> String {
+    indices
+        .iter()
+        .filter_map(|i| quiz.choices.get(*i))
+        .map(|c| c.as_ref())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn line_texts(quiz: &Quiz, order: &[usize]) -> String {
+    order
+        .iter()
+        .filter_map(|i| quiz.choices.get(*i))
+        .map(|c| c.as_ref())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Default for QuizState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl QuizState {
     pub fn new() -> Self {
         Self {
             current: 0,
             score: 0,
+            points: 0.0,
+            credit: Credit::default(),
+            choice_seed: None,
+            selection: None,
+            feedback: None,
+            history: Vec::new(),
+            question_started_at: None,
+            started_at: None,
+            question_limit: None,
+            total_limit: None,
+            exam: false,
+            drafts: BTreeMap::new(),
+            flagged: BTreeSet::new(),
             finished: false,
         }
     }
 
+    pub fn with_credit(mut self, credit: Credit) -> Self {
+        self.credit = credit;
+        self
+    }
+
+    pub fn with_shuffled_choices(mut self, seed: u64) -> Self {
+        self.choice_seed = Some(seed);
+        self
+    }
+
+    /// Limits the time per question and for the whole quiz. Questions that
+    /// run out of time count as unanswered.
+    pub fn with_time_limits(mut self, question: Option<Duration>, total: Option<Duration>) -> Self {
+        self.question_limit = question;
+        self.total_limit = total;
+        self
+    }
+
+    pub fn as_exam(mut self) -> Self {
+        self.exam = true;
+        self
+    }
+
+    /// See [`choice_order`].
+    pub fn choice_order(&self, quiz: &Quiz) -> Vec<usize> {
+        choice_order(quiz, self.choice_seed)
+    }
+
+    /// Choices in the order shown this session, each with its authored
+    /// index, which is what responses should use.
+    pub fn presented_choices<'a>(&self, quiz: &'a Quiz) -> Vec<(usize, &'a str)> {
+        self.choice_order(quiz)
+            .into_iter()
+            .map(|i| (i, quiz.choices[i].as_ref()))
+            .collect()
+    }
+
     pub fn current_quiz<'a>(&self, quizzes: &'a [Quiz]) -> Option<&'a Quiz> {
         if self.finished {
             None
@@ -33,7 +369,140 @@ impl QuizState {
         }
     }
 
+    pub fn phase(&self) -> Phase {
+        if self.finished {
+            Phase::Finished
+        } else if self.feedback.is_some() {
+            Phase::Submitted
+        } else {
+            Phase::Answering
+        }
+    }
+
+    /// Replaces the pending answer. Ignored once the question is submitted.
+    pub fn select(&mut self, response: Response) {
+        if self.phase() == Phase::Answering {
+            self.selection = Some(response);
+        }
+    }
+
+    /// Picks the choice with authored index `index`: replaces the choice of
+    /// a single-answer question, toggles it on a "select all that apply"
+    /// question and appends it to an ordering.
+    pub fn select_choice(&mut self, quiz: &Quiz, index: usize) {
+        let response = match (&quiz.correct, self.selection.take()) {
+            (Answer::Multiple(_), Some(Response::Choices(mut chosen))) => {
+                match chosen.iter().position(|&c| c == index) {
+                    Some(pos) => {
+                        chosen.remove(pos);
+                    }
+                    None => chosen.push(index),
+                }
+                Response::Choices(chosen)
+            }
+            (Answer::Multiple(_), _) => Response::Choices(vec![index]),
+            (Answer::Order { .. }, Some(Response::Order(mut order))) => {
+                if !order.contains(&index) {
+                    order.push(index);
+                }
+                Response::Order(order)
+            }
+            (Answer::Order { .. }, _) => Response::Order(vec![index]),
+            _ => Response::Choice(index),
+        };
+        self.select(response);
+    }
+
+    /// Sets the text typed into blank `blank` (0-based) of `quiz`.
+    pub fn fill_blank(&mut self, quiz: &Quiz, blank: usize, text: String) {
+        let mut filled = match self.selection.take() {
+            Some(Response::Blanks(filled)) => filled,
+            _ => Vec::new(),
+        };
+        if filled.len() < quiz.blank_count() {
+            filled.resize(quiz.blank_count(), String::new());
+        }
+        if let Some(slot) = filled.get_mut(blank) {
+            *slot = text;
+        }
+        self.select(Response::Blanks(filled));
+    }
+
+    pub fn clear_selection(&mut self) {
+        if self.phase() == Phase::Answering {
+            self.selection = None;
+        }
+    }
+
+    /// Whether the authored choice `index` is part of the pending answer.
+    pub fn is_selected(&self, index: usize) -> bool {
+        match &self.selection {
+            Some(Response::Choice(choice)) => *choice == index,
+            Some(Response::Choices(chosen) | Response::Order(chosen)) => chosen.contains(&index),
+            _ => false,
+        }
+    }
+
+    /// Whether the pending answer is complete enough to submit for `quiz`.
+    pub fn can_submit(&self, quiz: &Quiz) -> bool {
+        if self.phase() != Phase::Answering {
+            return false;
+        }
+        match &self.selection {
+            Some(Response::Choice(_)) => true,
+            Some(Response::Choices(chosen)) => !chosen.is_empty(),
+            Some(Response::Text(text)) => !text.trim().is_empty(),
+            Some(Response::Order(order)) => order.len() == quiz.choices.len(),
+            Some(Response::Blanks(filled)) => {
+                filled.len() == quiz.blank_count() && filled.iter().all(|b| !b.trim().is_empty())
+            }
+            None => false,
+        }
+    }
+
     pub fn answer(&mut self, quizzes: &[Quiz], choice: usize) -> AnswerResult {
+        self.submit(quizzes, Response::Choice(choice))
+    }
+
+    /// Grades `response` and moves straight on to the next question.
+    pub fn submit(&mut self, quizzes: &[Quiz], response: Response) -> AnswerResult {
+        self.select(response);
+        let result = self.submit_selection(quizzes);
+        if result != AnswerResult::Finished {
+            self.next(quizzes);
+        }
+        result
+    }
+
+    /// Notes that the current question is being shown now.
+    pub fn start_question(&mut self, clock: &impl Clock) {
+        let now = clock.now_millis();
+        self.question_started_at = Some(now);
+        self.started_at.get_or_insert(now);
+    }
+
+    /// Grades the pending answer and records the feedback, staying on the
+    /// question until [`next`](Self::next) is called. Submitting nothing
+    /// counts as wrong.
+    pub fn submit_selection(&mut self, quizzes: &[Quiz]) -> AnswerResult {
+        self.grade_selection(quizzes, None)
+    }
+
+    /// Like [`submit_selection`](Self::submit_selection), also recording how
+    /// long the answer took. An answer submitted after a time limit ran out
+    /// is not counted.
+    pub fn submit_selection_at(&mut self, quizzes: &[Quiz], clock: &impl Clock) -> AnswerResult {
+        match self.check_time(quizzes, clock) {
+            Some(Timeout::Total) => AnswerResult::Finished,
+            Some(Timeout::Question) => AnswerResult::Wrong,
+            None => self.grade_selection(quizzes, Some(clock.now_millis())),
+        }
+    }
+
+    fn grade_selection(&mut self, quizzes: &[Quiz], now: Option<u64>) -> AnswerResult {
+        if let Some(feedback) = &self.feedback {
+            return feedback.result;
+        }
         if self.finished {
             return AnswerResult::Finished;
         }
@@ -46,20 +515,210 @@ impl QuizState {
             }
         };
 
-        let result = if quiz.correct == choice {
+        let response = self.selection.take().unwrap_or(Response::Choices(Vec::new()));
+        let time_taken = self
+            .question_started_at
+            .zip(now)
+            .map(|(started, now)| now.saturating_sub(started));
+        let (result, earned) = self.record(quiz, &response, time_taken, false);
+
+        self.feedback = Some(Feedback {
+            result,
+            earned,
+            response,
+            timed_out: false,
+        });
+        result
+    }
+
+    /// Grades `response`, adding it to the score and the history. Timed out
+    /// answers earn nothing.
+    fn record(&mut self, quiz: &Quiz, response: &Response, time_taken: Option<u64>, timed_out: bool) -> (AnswerResult, f64) {
+        let earned = if timed_out { 0.0 } else { grade(quiz, response, self.credit) };
+        self.points += earned;
+
+        let result = if earned >= 1.0 {
             self.score += 1;
             AnswerResult::Correct
+        } else if earned > 0.0 {
+            AnswerResult::Partial
         } else {
             AnswerResult::Wrong
         };
 
+        self.history.push(Attempt {
+            quiz_id: quiz.id,
+            response: response.clone(),
+            result,
+            earned,
+            time_taken,
+            timed_out,
+        });
+        (result, earned)
+    }
+
+    fn question_time_left(&self, now: u64) -> Option<u64> {
+        if self.exam || self.feedback.is_some() {
+            return None;
+        }
+        let limit = self.question_limit?.as_millis() as u64;
+        let started = self.question_started_at?;
+        Some(limit.saturating_sub(now.saturating_sub(started)))
+    }
+
+    fn total_time_left(&self, now: u64) -> Option<u64> {
+        let limit = self.total_limit?.as_millis() as u64;
+        let started = self.started_at?;
+        Some(limit.saturating_sub(now.saturating_sub(started)))
+    }
+
+    /// Time left before the next limit runs out, if any limit applies.
+    pub fn time_left(&self, clock: &impl Clock) -> Option<Duration> {
+        if self.finished {
+            return None;
+        }
+        let now = clock.now_millis();
+        [self.question_time_left(now), self.total_time_left(now)]
+            .into_iter()
+            .flatten()
+            .min()
+            .map(Duration::from_millis)
+    }
+
+    /// Applies any time limit that has run out. A question out of time is
+    /// recorded as unanswered and shows its feedback; when the whole quiz is
+    /// out of time every remaining question is recorded that way (an exam
+    /// is submitted as it stands) and the quiz finishes.
+    pub fn check_time(&mut self, quizzes: &[Quiz], clock: &impl Clock) -> Option<Timeout> {
+        if self.finished {
+            return None;
+        }
+        let now = clock.now_millis();
+
+        if self.total_time_left(now) == Some(0) {
+            if self.exam {
+                self.submit_exam(quizzes);
+            } else {
+                let from = if self.feedback.is_some() { self.current + 1 } else { self.current };
+                for quiz in quizzes.iter().skip(from) {
+                    self.record(quiz, &Response::Choices(Vec::new()), None, true);
+                }
+                self.selection = None;
+                self.feedback = None;
+                self.current = quizzes.len();
+                self.finished = true;
+            }
+            return Some(Timeout::Total);
+        }
+
+        if self.question_time_left(now) == Some(0) {
+            let quiz = quizzes.get(self.current)?;
+            let response = Response::Choices(Vec::new());
+            let time_taken = self.question_limit.map(|limit| limit.as_millis() as u64);
+            self.selection = None;
+            let (result, earned) = self.record(quiz, &response, time_taken, true);
+            self.feedback = Some(Feedback {
+                result,
+                earned,
+                response,
+                timed_out: true,
+            });
+            return Some(Timeout::Question);
+        }
+
+        None
+    }
+
+    /// Moves to the question at position `index` in exam mode, keeping the
+    /// answer given so far and bringing back any answer saved for `index`.
+    pub fn go_to(&mut self, quizzes: &[Quiz], index: usize) {
+        if !self.exam || self.finished || index >= quizzes.len() {
+            return;
+        }
+        match self.selection.take() {
+            Some(response) => self.drafts.insert(self.current, response),
+            None => self.drafts.remove(&self.current),
+        };
+        self.current = index;
+        self.selection = self.drafts.remove(&index);
+    }
+
+    /// Moves on without answering, wrapping around after the last question.
+    pub fn skip(&mut self, quizzes: &[Quiz]) {
+        if !quizzes.is_empty() {
+            self.go_to(quizzes, (self.current + 1) % quizzes.len());
+        }
+    }
+
+    pub fn back(&mut self, quizzes: &[Quiz]) {
+        if self.current > 0 {
+            self.go_to(quizzes, self.current - 1);
+        }
+    }
+
+    pub fn toggle_flag(&mut self) {
+        if !self.flagged.remove(&self.current) {
+            self.flagged.insert(self.current);
+        }
+    }
+
+    pub fn is_flagged(&self, index: usize) -> bool {
+        self.flagged.contains(&index)
+    }
+
+    /// Whether the question at position `index` has an answer, saved or
+    /// pending.
+    pub fn is_answered(&self, index: usize) -> bool {
+        if index == self.current {
+            self.selection.is_some()
+        } else {
+            self.drafts.contains_key(&index)
+        }
+    }
+
+    /// Positions of the questions still without an answer.
+    pub fn unanswered(&self, quizzes: &[Quiz]) -> Vec<usize> {
+        (0..quizzes.len()).filter(|i| !self.is_answered(*i)).collect()
+    }
+
+    /// The first unanswered question after the current one, wrapping around.
+    pub fn next_unanswered(&self, quizzes: &[Quiz]) -> Option<usize> {
+        let total = quizzes.len();
+        (1..=total)
+            .map(|step| (self.current + step) % total)
+            .find(|i| !self.is_answered(*i))
+    }
+
+    /// Ends an exam, grading every question in order. Unanswered questions
+    /// count as wrong.
+    pub fn submit_exam(&mut self, quizzes: &[Quiz]) {
+        if !self.exam || self.finished {
+            return;
+        }
+        if let Some(response) = self.selection.take() {
+            self.drafts.insert(self.current, response);
+        }
+        for (index, quiz) in quizzes.iter().enumerate() {
+            let response = self.drafts.remove(&index).unwrap_or(Response::Choices(Vec::new()));
+            self.record(quiz, &response, None, false);
+        }
+        self.current = quizzes.len();
+        self.finished = true;
+    }
+
+    /// Leaves the submitted question for the next one, finishing after the
+    /// last. Does nothing while the current question is unanswered.
+    pub fn next(&mut self, quizzes: &[Quiz]) {
+        if self.feedback.take().is_none() {
+            return;
+        }
+        self.selection = None;
+        self.question_started_at = None;
         self.current += 1;
 
         if self.current >= quizzes.len() {
             self.finished = true;
         }
-
-        result
     }
 
     pub fn progress(&self, total: usize) -> (usize, usize) {
@@ -67,7 +726,38 @@ impl QuizState {
     }
 
     pub fn reset(&mut self) {
-        *self = Self::new();
+        *self = Self {
+            credit: self.credit,
+            choice_seed: self.choice_seed,
+            question_limit: self.question_limit,
+            total_limit: self.total_limit,
+            exam: self.exam,
+            ..Self::new()
+        };
+    }
+
+    /// The questions whose last answer was not fully correct, timed out ones
+    /// included, in play order.
+    pub fn mistakes(&self, quizzes: &[Quiz]) -> Vec<Quiz> {
+        quizzes
+            .iter()
+            .filter(|q| {
+                self.history
+                    .iter()
+                    .rev()
+                    .find(|attempt| attempt.quiz_id == q.id)
+                    .is_some_and(|attempt| attempt.result != AnswerResult::Correct)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// A new quiz over just the [`mistakes`](QuizState::mistakes), keeping
+    /// this one's settings.
+    pub fn retry_mistakes(&self, quizzes: &[Quiz]) -> (Vec<Quiz>, QuizState) {
+        let mut fresh = self.clone();
+        fresh.reset();
+        (self.mistakes(quizzes), fresh)
     }
 }
 
@@ -75,19 +765,26 @@ impl QuizState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::quiz::{Quiz, Level};
+    use crate::quiz::{Answer, Quiz, Level};
+    use crate::quiz::{Matching, TextAnswer};
+    use std::cell::Cell;
 
     fn mock_quiz(correct: usize) -> Quiz {
+        mock_quiz_with(Answer::Single(correct))
+    }
+
+    fn mock_quiz_with(correct: Answer) -> Quiz {
         Quiz {
             id: 1,
-            title: "Test",
-            question: "Q?",
+            title: "Test".into(),
+            question: "Q?".into(),
             code: None,
-            choices: vec!["A", "B", "C"],
+            choices: vec!["A".into(), "B".into(), "C".into()],
             correct,
-            explanation: "Because.",
+            explanation: "Because.".into(),
             tags: vec![],
             level: Level::Intro,
+            expect: None,
         }
     }
 
@@ -139,4 +836,442 @@ mod tests {
         assert_eq!(state.score, 0);
         assert!(!state.finished);
     }
+
+    #[test]
+    fn test_multiple_answer_needs_exact_set() {
+        let quizzes = vec![mock_quiz_with(Answer::Multiple(vec![0, 2]))];
+        let mut state = QuizState::new();
+
+        let result = state.submit(&quizzes, Response::Choices(vec![2, 0]));
+
+        assert_eq!(result, AnswerResult::Correct);
+        assert_eq!(state.score, 1);
+    }
+
+    #[test]
+    fn test_exact_credit_rejects_subset() {
+        let quizzes = vec![mock_quiz_with(Answer::Multiple(vec![0, 2]))];
+        let mut state = QuizState::new();
+
+        let result = state.submit(&quizzes, Response::Choices(vec![0]));
+
+        assert_eq!(result, AnswerResult::Wrong);
+        assert_eq!(state.points, 0.0);
+    }
+
+    #[test]
+    fn test_partial_credit_for_subset() {
+        let quizzes = vec![mock_quiz_with(Answer::Multiple(vec![0, 2]))];
+        let mut state = QuizState::new().with_credit(Credit::Partial);
+
+        let result = state.submit(&quizzes, Response::Choices(vec![0]));
+
+        assert_eq!(result, AnswerResult::Partial);
+        assert_eq!(state.score, 0);
+        assert_eq!(state.points, 0.5);
+    }
+
+    #[test]
+    fn test_partial_credit_wrong_choices_cancel_hits() {
+        let quiz = mock_quiz_with(Answer::Multiple(vec![0, 2]));
+
+        assert_eq!(grade(&quiz, &Response::Choices(vec![0, 1]), Credit::Partial), 0.0);
+        assert_eq!(grade(&quiz, &Response::Choices(vec![1]), Credit::Partial), 0.0);
+        assert_eq!(grade(&quiz, &Response::Choice(2), Credit::Partial), 0.5);
+    }
+
+    fn text_answer(accepted: &[&'static str], matching: Matching) -> TextAnswer {
+        TextAnswer {
+            accepted: accepted.iter().map(|a| (*a).into()).collect(),
+            matching,
+        }
+    }
+
+    #[test]
+    fn test_text_answer_scores() {
+        let quizzes = vec![mock_quiz_with(Answer::Text(text_answer(&["12"], Matching::Normalized)))];
+        let mut state = QuizState::new();
+
+        let result = state.submit(&quizzes, Response::Text(" 12\n".to_string()));
+
+        assert_eq!(result, AnswerResult::Correct);
+        assert_eq!(state.score, 1);
+    }
+
+    #[test]
+    fn test_text_matching_rules() {
+        let exact = text_answer(&["Hello,  Bob!"], Matching::Exact);
+        assert!(text_matches(&exact, "  Hello,  Bob!\n"));
+        assert!(!text_matches(&exact, "Hello, Bob!"));
+
+        let normalized = text_answer(&["Hello,  Bob!"], Matching::Normalized);
+        assert!(text_matches(&normalized, "Hello, Bob!"));
+        assert!(!text_matches(&normalized, "hello, bob!"));
+
+        let case_insensitive = text_answer(&["Hello, Bob!"], Matching::CaseInsensitive);
+        assert!(text_matches(&case_insensitive, "hello,   BOB!"));
+
+        let regex = text_answer(&[r"error\[E0382\].*"], Matching::Regex);
+        assert!(text_matches(&regex, "error[E0382]: use of moved value"));
+        assert!(!text_matches(&regex, "warning: error[E0382]"));
+    }
+
+    #[test]
+    fn test_any_accepted_answer_matches() {
+        let answer = text_answer(&["i32", "32-bit signed integer"], Matching::CaseInsensitive);
+
+        assert!(text_matches(&answer, "I32"));
+        assert!(text_matches(&answer, "32-bit Signed Integer"));
+        assert!(!text_matches(&answer, "u32"));
+    }
+
+    #[test]
+    fn test_choice_response_to_text_question_is_wrong() {
+        let quiz = mock_quiz_with(Answer::Text(text_answer(&["1"], Matching::Exact)));
+
+        assert_eq!(grade(&quiz, &Response::Choice(1), Credit::Partial), 0.0);
+    }
+
+    #[test]
+    fn test_order_answer() {
+        let mut quiz = mock_quiz_with(Answer::Order { order: vec![2, 0, 1] });
+        quiz.choices = vec!["b".into(), "c".into(), "a".into()];
+
+        assert_eq!(grade(&quiz, &Response::Order(vec![2, 0, 1]), Credit::Exact), 1.0);
+        assert_eq!(grade(&quiz, &Response::Order(vec![2, 1, 0]), Credit::Exact), 0.0);
+        assert!((grade(&quiz, &Response::Order(vec![2, 1, 0]), Credit::Partial) - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(grade(&quiz, &Response::Order(vec![2, 0]), Credit::Partial), 0.0);
+    }
+
+    #[test]
+    fn test_order_accepts_swapped_identical_lines() {
+        let mut quiz = mock_quiz_with(Answer::Order { order: vec![0, 1, 2] });
+        quiz.choices = vec!["fn main() {".into(), "}".into(), "}".into()];
+
+        assert_eq!(grade(&quiz, &Response::Order(vec![0, 2, 1]), Credit::Exact), 1.0);
+    }
+
+    #[test]
+    fn test_blanks_answer() {
+        let quiz = mock_quiz_with(Answer::Blanks {
+            blanks: vec![
+                text_answer(&["mut"], Matching::Exact),
+                text_answer(&["'a"], Matching::Exact),
+            ],
+        });
+        let mut state = QuizState::new().with_credit(Credit::Partial);
+
+        let result = state.submit(
+            std::slice::from_ref(&quiz),
+            Response::Blanks(vec!["mut".to_string(), "'b".to_string()]),
+        );
+
+        assert_eq!(result, AnswerResult::Partial);
+        assert_eq!(state.points, 0.5);
+        assert_eq!(grade(&quiz, &Response::Blanks(vec!["mut".into(), " 'a ".into()]), Credit::Exact), 1.0);
+    }
+
+    #[test]
+    fn test_submit_then_next_phases() {
+        let quizzes = vec![mock_quiz(1), mock_quiz(0)];
+        let mut state = QuizState::new();
+
+        assert_eq!(state.phase(), Phase::Answering);
+        assert!(!state.can_submit(&quizzes[0]));
+        state.select_choice(&quizzes[0], 2);
+        state.select_choice(&quizzes[0], 1);
+        assert!(state.is_selected(1) && !state.is_selected(2));
+
+        assert_eq!(state.submit_selection(&quizzes), AnswerResult::Correct);
+        assert_eq!(state.phase(), Phase::Submitted);
+        assert_eq!(state.current, 0);
+        assert_eq!(state.feedback.as_ref().unwrap().response, Response::Choice(1));
+
+        // Changing or resubmitting the answer has no effect after submitting.
+        state.select_choice(&quizzes[0], 0);
+        assert_eq!(state.submit_selection(&quizzes), AnswerResult::Correct);
+        assert_eq!(state.score, 1);
+
+        state.next(&quizzes);
+        assert_eq!(state.phase(), Phase::Answering);
+        assert_eq!(state.current, 1);
+        assert!(state.selection.is_none());
+
+        state.submit_selection(&quizzes);
+        state.next(&quizzes);
+        assert_eq!(state.phase(), Phase::Finished);
+        assert_eq!(state.score, 1);
+    }
+
+    #[test]
+    fn test_next_needs_a_submitted_answer() {
+        let quizzes = vec![mock_quiz(1), mock_quiz(0)];
+        let mut state = QuizState::new();
+
+        state.next(&quizzes);
+
+        assert_eq!(state.current, 0);
+    }
+
+    #[test]
+    fn test_select_choice_toggles_multiple() {
+        let quiz = mock_quiz_with(Answer::Multiple(vec![0, 2]));
+        let mut state = QuizState::new();
+
+        state.select_choice(&quiz, 0);
+        state.select_choice(&quiz, 1);
+        state.select_choice(&quiz, 2);
+        state.select_choice(&quiz, 1);
+
+        assert_eq!(state.selection, Some(Response::Choices(vec![0, 2])));
+    }
+
+    #[test]
+    fn test_select_choice_builds_order() {
+        let quiz = mock_quiz_with(Answer::Order { order: vec![2, 0, 1] });
+        let mut state = QuizState::new();
+
+        state.select_choice(&quiz, 2);
+        state.select_choice(&quiz, 2);
+        state.select_choice(&quiz, 0);
+        assert!(!state.can_submit(&quiz));
+        state.select_choice(&quiz, 1);
+
+        assert_eq!(state.selection, Some(Response::Order(vec![2, 0, 1])));
+        assert!(state.can_submit(&quiz));
+    }
+
+    #[test]
+    fn test_fill_blank_needs_every_blank() {
+        let mut quiz = mock_quiz_with(Answer::Blanks {
+            blanks: vec![
+                text_answer(&["mut"], Matching::Exact),
+                text_answer(&["'a"], Matching::Exact),
+            ],
+        });
+        quiz.code = Some("let ____ x: &____ str;".into());
+        let mut state = QuizState::new();
+
+        state.fill_blank(&quiz, 1, "'a".to_string());
+        assert!(!state.can_submit(&quiz));
+        state.fill_blank(&quiz, 0, "mut".to_string());
+
+        assert!(state.can_submit(&quiz));
+        assert_eq!(state.submit_selection(std::slice::from_ref(&quiz)), AnswerResult::Correct);
+    }
+
+    #[test]
+    fn test_history_records_every_attempt() {
+        let quizzes = vec![mock_quiz(1), mock_quiz_with(Answer::Multiple(vec![0, 2]))];
+        let mut state = QuizState::new();
+
+        state.start_question(&|| 1_000);
+        state.select_choice(&quizzes[0], 1);
+        state.submit_selection_at(&quizzes, &|| 4_500);
+        state.next(&quizzes);
+        state.submit(&quizzes, Response::Choices(vec![0]));
+
+        assert_eq!(state.history.len(), 2);
+        assert_eq!(state.history[0].response, Response::Choice(1));
+        assert_eq!(state.history[0].result, AnswerResult::Correct);
+        assert_eq!(state.history[0].time_taken, Some(3_500));
+        assert_eq!(state.history[1].result, AnswerResult::Wrong);
+        assert_eq!(state.history[1].time_taken, None);
+    }
+
+    #[test]
+    fn test_describe_answers() {
+        let quiz = mock_quiz_with(Answer::Multiple(vec![0, 2]));
+
+        assert_eq!(describe_solution(&quiz), "A; C");
+        assert_eq!(describe_response(&quiz, &Response::Choice(1)), "B");
+        assert_eq!(describe_response(&quiz, &Response::Choices(vec![])), "(no answer)");
+
+        let blanks = mock_quiz_with(Answer::Blanks {
+            blanks: vec![text_answer(&["mut"], Matching::Exact), text_answer(&["'a"], Matching::Exact)],
+        });
+        assert_eq!(describe_solution(&blanks), "mut, 'a");
+    }
+
+    #[test]
+    fn test_exam_answers_can_be_changed_before_submitting() {
+        let quizzes = vec![mock_quiz(0), mock_quiz(1), mock_quiz(2)];
+        let mut state = QuizState::new().as_exam();
+
+        state.select_choice(&quizzes[0], 1);
+        state.skip(&quizzes);
+        state.skip(&quizzes);
+        state.select_choice(&quizzes[2], 2);
+        state.back(&quizzes);
+        state.back(&quizzes);
+        assert_eq!(state.selection, Some(Response::Choice(1)));
+        state.select_choice(&quizzes[0], 0);
+
+        assert_eq!(state.unanswered(&quizzes), vec![1]);
+        assert_eq!(state.next_unanswered(&quizzes), Some(1));
+        assert_eq!(state.score, 0);
+
+        state.submit_exam(&quizzes);
+
+        assert!(state.finished);
+        assert_eq!(state.score, 2);
+        assert_eq!(state.history.len(), 3);
+        assert_eq!(state.history[1].result, AnswerResult::Wrong);
+    }
+
+    #[test]
+    fn test_skip_wraps_and_flags_toggle() {
+        let quizzes = vec![mock_quiz(0), mock_quiz(1)];
+        let mut state = QuizState::new().as_exam();
+
+        state.skip(&quizzes);
+        state.toggle_flag();
+        state.skip(&quizzes);
+
+        assert_eq!(state.current, 0);
+        assert!(state.is_flagged(1));
+        state.go_to(&quizzes, 1);
+        state.toggle_flag();
+        assert!(!state.is_flagged(1));
+    }
+
+    #[test]
+    fn test_navigation_needs_exam_mode() {
+        let quizzes = vec![mock_quiz(0), mock_quiz(1)];
+        let mut state = QuizState::new();
+
+        state.skip(&quizzes);
+        state.submit_exam(&quizzes);
+
+        assert_eq!(state.current, 0);
+        assert!(!state.finished);
+    }
+
+    #[test]
+    fn test_question_time_limit() {
+        let quizzes = vec![mock_quiz(1), mock_quiz(0)];
+        let now = Cell::new(0);
+        let clock = || now.get();
+        let mut state = QuizState::new().with_time_limits(Some(Duration::from_secs(10)), None);
+
+        state.start_question(&clock);
+        now.set(4_000);
+        assert_eq!(state.time_left(&clock), Some(Duration::from_secs(6)));
+        assert_eq!(state.check_time(&quizzes, &clock), None);
+
+        now.set(12_000);
+        state.select_choice(&quizzes[0], 1);
+        let result = state.submit_selection_at(&quizzes, &clock);
+
+        assert_eq!(result, AnswerResult::Wrong);
+        assert_eq!(state.score, 0);
+        assert!(state.feedback.as_ref().unwrap().timed_out);
+        assert!(state.history[0].timed_out);
+        assert_eq!(state.history[0].time_taken, Some(10_000));
+
+        state.next(&quizzes);
+        state.start_question(&clock);
+        state.select_choice(&quizzes[1], 0);
+        assert_eq!(state.submit_selection_at(&quizzes, &clock), AnswerResult::Correct);
+    }
+
+    #[test]
+    fn test_total_time_limit_finishes_the_quiz() {
+        let quizzes = vec![mock_quiz(1), mock_quiz(0), mock_quiz(2)];
+        let now = Cell::new(0);
+        let clock = || now.get();
+        let mut state = QuizState::new().with_time_limits(None, Some(Duration::from_secs(60)));
+
+        state.start_question(&clock);
+        state.select_choice(&quizzes[0], 1);
+        state.submit_selection_at(&quizzes, &clock);
+        now.set(61_000);
+
+        assert_eq!(state.check_time(&quizzes, &clock), Some(Timeout::Total));
+        assert!(state.finished);
+        assert_eq!(state.score, 1);
+        assert_eq!(state.history.len(), 3);
+        assert!(state.history[1].timed_out && state.history[2].timed_out);
+        assert_eq!(state.time_left(&clock), None);
+    }
+
+    #[test]
+    fn test_total_time_limit_submits_an_exam() {
+        let quizzes = vec![mock_quiz(1), mock_quiz(0)];
+        let now = Cell::new(0);
+        let clock = || now.get();
+        let mut state = QuizState::new()
+            .as_exam()
+            .with_time_limits(Some(Duration::from_secs(1)), Some(Duration::from_secs(30)));
+
+        state.start_question(&clock);
+        state.select_choice(&quizzes[0], 1);
+        now.set(5_000);
+        assert_eq!(state.check_time(&quizzes, &clock), None);
+        now.set(30_000);
+        state.check_time(&quizzes, &clock);
+
+        assert!(state.finished);
+        assert_eq!(state.score, 1);
+    }
+
+    #[test]
+    fn test_choice_order_defaults_to_authored() {
+        let quiz = mock_quiz(1);
+
+        assert_eq!(QuizState::new().choice_order(&quiz), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_shuffled_choices_keep_original_indices() {
+        let mut quiz = mock_quiz(3);
+        quiz.choices = (0..8).map(|i| format!("choice {}", i).into()).collect();
+        let quizzes = vec![quiz];
+        let mut state = QuizState::new().with_shuffled_choices(99);
+
+        let presented = state.presented_choices(&quizzes[0]);
+        let shown: Vec<usize> = presented.iter().map(|(i, _)| *i).collect();
+
+        assert_ne!(shown, (0..8).collect::<Vec<_>>());
+        assert!(presented.iter().all(|(i, text)| *text == format!("choice {}", i)));
+        assert_eq!(state.answer(&quizzes, 3), AnswerResult::Correct);
+    }
+
+    #[test]
+    fn test_choice_order_is_stable_per_seed() {
+        let mut quiz = mock_quiz(0);
+        quiz.choices = (0..6).map(|i| format!("{}", i).into()).collect();
+
+        assert_eq!(choice_order(&quiz, Some(5)), choice_order(&quiz, Some(5)));
+    }
+
+    #[test]
+    fn test_typed_answers_are_not_shuffled() {
+        let quiz = mock_quiz_with(Answer::Text(text_answer(&["1"], Matching::Exact)));
+
+        assert_eq!(choice_order(&quiz, Some(5)), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_retry_mistakes() {
+        let quizzes: Vec<Quiz> = (1..=4)
+            .map(|id| Quiz {
+                id,
+                ..mock_quiz_with(Answer::Multiple(vec![0, 2]))
+            })
+            .collect();
+        let mut state = QuizState::new().with_credit(Credit::Partial).with_shuffled_choices(3);
+        state.submit(&quizzes, Response::Choices(vec![0, 2]));
+        state.submit(&quizzes, Response::Choices(vec![1]));
+        state.submit(&quizzes, Response::Choices(vec![0]));
+        state.submit(&quizzes, Response::Choices(vec![2, 0]));
+
+        let (retry, fresh) = state.retry_mistakes(&quizzes);
+
+        assert_eq!(retry.iter().map(|q| q.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(fresh.current, 0);
+        assert!(fresh.history.is_empty());
+        assert_eq!(fresh.credit, Credit::Partial);
+        assert_eq!(fresh.choice_seed, Some(3));
+    }
 }
@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{AnswerResult, QuizState};
+use crate::quiz::Quiz;
+
+/// How many questions a pass needs answered correctly.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Threshold {
+    Count(usize),
+    /// Of the questions asked, from 0 to 100.
+    Percent(f64),
+}
+
+impl Threshold {
+    pub fn is_met(self, correct: usize, total: usize) -> bool {
+        match self {
+            Threshold::Count(needed) => correct >= needed,
+            // Compared without dividing, so 7 of 10 meets 70% exactly.
+            Threshold::Percent(percent) => correct as f64 * 100.0 >= percent * total as f64,
+        }
+    }
+}
+
+impl fmt::Display for Threshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Threshold::Count(needed) => write!(f, "{}", needed),
+            Threshold::Percent(percent) => write!(f, "{}%", percent),
+        }
+    }
+}
+
+impl FromStr for Threshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(percent) = s.strip_suffix('%') {
+            return match percent.trim().parse::<f64>() {
+                Ok(percent) if (0.0..=100.0).contains(&percent) => Ok(Threshold::Percent(percent)),
+                _ => Err(format!("{:?} is not a percentage from 0% to 100%", s)),
+            };
+        }
+        s.parse()
+            .map(Threshold::Count)
+            .map_err(|_| format!("{:?} is not a number of questions or a percentage", s))
+    }
+}
+
+/// What a quiz must reach to pass, overall or among the questions with one
+/// tag. Written `80%`, `7` or `ownership=80%`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PassMark {
+    pub tag: Option<String>,
+    pub threshold: Threshold,
+}
+
+impl fmt::Display for PassMark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.tag {
+            Some(tag) => write!(f, "{}={}", tag, self.threshold),
+            None => write!(f, "{}", self.threshold),
+        }
+    }
+}
+
+impl FromStr for PassMark {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((tag, threshold)) => {
+                let tag = tag.trim();
+                if tag.is_empty() {
+                    return Err(format!("pass mark {:?} has no tag before `=`", s));
+                }
+                Ok(PassMark { tag: Some(tag.to_string()), threshold: threshold.parse()? })
+            }
+            None => Ok(PassMark { tag: None, threshold: s.parse()? }),
+        }
+    }
+}
+
+/// How a quiz did against one pass mark.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Check {
+    /// The pass mark as written.
+    pub mark: String,
+    pub tag: Option<String>,
+    pub correct: usize,
+    /// Questions asked that the mark covers.
+    pub total: usize,
+    pub passed: bool,
+}
+
+/// Whether a quiz passed, with the checks behind it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Verdict {
+    /// Every check passed.
+    pub passed: bool,
+    pub score: usize,
+    pub total: usize,
+    pub percent: f64,
+    pub checks: Vec<Check>,
+}
+
+impl Verdict {
+    /// Judges the answers in `state` against `marks`. Only fully correct
+    /// answers count, as in the plain score. A tag none of the questions
+    /// carry fails a percentage mark, since nothing was shown to be known.
+    pub fn judge(marks: &[PassMark], quizzes: &[Quiz], state: &QuizState) -> Self {
+        let correct_ids: HashSet<u32> = state
+            .history
+            .iter()
+            .filter(|attempt| attempt.result == AnswerResult::Correct)
+            .map(|attempt| attempt.quiz_id)
+            .collect();
+        let tally = |covered: &[&Quiz]| {
+            let correct = covered.iter().filter(|quiz| correct_ids.contains(&quiz.id)).count();
+            (correct, covered.len())
+        };
+
+        let checks: Vec<Check> = marks
+            .iter()
+            .map(|mark| {
+                let covered: Vec<&Quiz> = quizzes
+                    .iter()
+                    .filter(|quiz| match &mark.tag {
+                        Some(wanted) => quiz.tags.iter().any(|tag| tag.eq_ignore_ascii_case(wanted)),
+                        None => true,
+                    })
+                    .collect();
+                let (correct, total) = tally(&covered);
+                let passed = match mark.threshold {
+                    Threshold::Percent(_) if total == 0 => false,
+                    threshold => threshold.is_met(correct, total),
+                };
+                Check { mark: mark.to_string(), tag: mark.tag.clone(), correct, total, passed }
+            })
+            .collect();
+
+        let (score, total) = tally(&quizzes.iter().collect::<Vec<_>>());
+        Verdict {
+            passed: checks.iter().all(|check| check.passed),
+            score,
+            total,
+            percent: if total == 0 { 0.0 } else { 100.0 * score as f64 / total as f64 },
+            checks,
+        }
+    }
+
+    /// One line of JSON, for scripts.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a verdict is always valid JSON")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Response;
+    use crate::quiz::quiz_bank;
+
+    #[test]
+    fn test_parse_pass_marks() {
+        assert_eq!("80%".parse(), Ok(PassMark { tag: None, threshold: Threshold::Percent(80.0) }));
+        assert_eq!("7".parse(), Ok(PassMark { tag: None, threshold: Threshold::Count(7) }));
+        assert_eq!(
+            "ownership=2".parse(),
+            Ok(PassMark { tag: Some("ownership".to_string()), threshold: Threshold::Count(2) })
+        );
+        assert_eq!("ownership=66.5%".parse::<PassMark>().unwrap().to_string(), "ownership=66.5%");
+        assert!("120%".parse::<PassMark>().is_err());
+        assert!("most".parse::<PassMark>().is_err());
+        assert!("=50%".parse::<PassMark>().is_err());
+    }
+
+    #[test]
+    fn test_percent_is_compared_exactly() {
+        assert!(Threshold::Percent(70.0).is_met(7, 10));
+        assert!(!Threshold::Percent(70.0).is_met(6, 10));
+        assert!(Threshold::Count(3).is_met(3, 10));
+    }
+
+    #[test]
+    fn test_judge_overall_and_per_tag() {
+        let quizzes: Vec<Quiz> = quiz_bank().into_iter().take(4).collect();
+        let tag = quizzes[0].tags[0].to_string();
+        let mut state = QuizState::new();
+        state.submit(&quizzes, Response::Choice(quizzes[0].correct.correct_choices()[0]));
+        for _ in 1..quizzes.len() {
+            state.submit(&quizzes, Response::Choice(usize::MAX));
+        }
+
+        let marks: Vec<PassMark> = ["25%", &format!("{}=1", tag), "2"].iter().map(|m| m.parse().unwrap()).collect();
+        let verdict = Verdict::judge(&marks, &quizzes, &state);
+
+        assert_eq!((verdict.score, verdict.total, verdict.percent), (1, 4, 25.0));
+        let passed: Vec<bool> = verdict.checks.iter().map(|check| check.passed).collect();
+        assert_eq!(passed, [true, true, false]);
+        assert!(!verdict.passed);
+        assert!(verdict.to_json().starts_with(r#"{"passed":false,"score":1"#));
+    }
+
+    #[test]
+    fn test_unknown_tag_fails_a_percentage() {
+        let quizzes: Vec<Quiz> = quiz_bank().into_iter().take(1).collect();
+        let marks = ["no-such-tag=50%".parse().unwrap(), "no-such-tag=0".parse().unwrap()];
+        let verdict = Verdict::judge(&marks, &quizzes, &QuizState::new());
+
+        assert!(!verdict.checks[0].passed);
+        assert!(verdict.checks[1].passed);
+    }
+}
@@ -0,0 +1,341 @@
+use crate::quiz::BLANK;
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while", "yield",
+];
+
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    "f32", "f64",
+];
+
+/// What a piece of Rust code is, for choosing its color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// Whitespace, punctuation, operators and ordinary names.
+    Plain,
+    Keyword,
+    /// Primitive types and capitalized names.
+    Type,
+    /// A name followed by `(`.
+    Function,
+    /// A macro name with its `!`.
+    Macro,
+    /// String, byte string and character literals.
+    String,
+    Number,
+    Comment,
+    Lifetime,
+    /// `#[...]` and `#![...]`.
+    Attribute,
+    /// A fill-in-the-blank placeholder.
+    Blank,
+}
+
+impl Kind {
+    /// Lowercase name, used for CSS classes.
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Plain => "plain",
+            Kind::Keyword => "keyword",
+            Kind::Type => "type",
+            Kind::Function => "function",
+            Kind::Macro => "macro",
+            Kind::String => "string",
+            Kind::Number => "number",
+            Kind::Comment => "comment",
+            Kind::Lifetime => "lifetime",
+            Kind::Attribute => "attribute",
+            Kind::Blank => "blank",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: Kind,
+    pub text: &'a str,
+}
+
+/// Splits `code` into tokens that, joined back together, give `code`
+/// unchanged. This is a highlighter, not a parser: code that does not lex
+/// as Rust still comes back whole, just colored less helpfully.
+pub fn tokenize(code: &str) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut rest = code;
+    while !rest.is_empty() {
+        let (kind, len) = next_token(rest);
+        let (text, after) = rest.split_at(len);
+        match tokens.last_mut() {
+            // Runs of plain text become one token.
+            Some(last) if kind == Kind::Plain && last.kind == Kind::Plain => {
+                let start = code.len() - rest.len() - last.text.len();
+                last.text = &code[start..code.len() - after.len()];
+            }
+            _ => tokens.push(Token { kind, text }),
+        }
+        rest = after;
+    }
+    tokens
+}
+
+/// The tokens of `code` line by line, for numbering. Tokens spanning lines,
+/// such as block comments, are split at the line breaks, which are left
+/// out.
+pub fn lines(code: &str) -> Vec<Vec<Token<'_>>> {
+    let mut lines = vec![Vec::new()];
+    for token in tokenize(code) {
+        for (i, part) in token.text.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Vec::new());
+            }
+            let part = part.strip_suffix('\r').unwrap_or(part);
+            if !part.is_empty() {
+                lines.last_mut().expect("there is always a line").push(Token { kind: token.kind, text: part });
+            }
+        }
+    }
+    // Like `str::lines`, a final line break does not start another line.
+    if code.ends_with('\n') {
+        lines.pop();
+    }
+    lines
+}
+
+/// The kind and byte length of the token at the start of `code`.
+fn next_token(code: &str) -> (Kind, usize) {
+    let first = code.chars().next().expect("code is not empty");
+
+    if code.starts_with(BLANK) {
+        return (Kind::Blank, BLANK.len());
+    }
+    if code.starts_with("//") {
+        return (Kind::Comment, code.find('\n').unwrap_or(code.len()));
+    }
+    if code.starts_with("/*") {
+        return (Kind::Comment, block_comment(code));
+    }
+    if code.starts_with("#[") || code.starts_with("#![") {
+        return (Kind::Attribute, attribute(code));
+    }
+    if let Some(len) = string(code) {
+        return (Kind::String, len);
+    }
+    if first == '\'' {
+        return match char_literal(code) {
+            Some(len) => (Kind::String, len),
+            None => (Kind::Lifetime, 1 + word(&code[1..])),
+        };
+    }
+    if first.is_ascii_digit() {
+        return (Kind::Number, number(code));
+    }
+    if first.is_alphabetic() || first == '_' {
+        let len = word(code);
+        let name = &code[..len];
+        let after = &code[len..];
+        if after.starts_with('!') && !after.starts_with("!=") {
+            return (Kind::Macro, len + 1);
+        }
+        let kind = if KEYWORDS.contains(&name) {
+            Kind::Keyword
+        } else if PRIMITIVES.contains(&name) || first.is_uppercase() {
+            Kind::Type
+        } else if after.starts_with('(') || after.starts_with("::<") {
+            Kind::Function
+        } else {
+            Kind::Plain
+        };
+        return (kind, len);
+    }
+    if first.is_whitespace() {
+        let len = code.find(|c: char| !c.is_whitespace()).unwrap_or(code.len());
+        return (Kind::Plain, len);
+    }
+    (Kind::Plain, first.len_utf8())
+}
+
+/// Length of the identifier or keyword at the start of `code`.
+fn word(code: &str) -> usize {
+    code.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(code.len())
+}
+
+fn block_comment(code: &str) -> usize {
+    let mut depth = 0;
+    let mut i = 0;
+    while i < code.len() {
+        let rest = &code[i..];
+        if rest.starts_with("/*") {
+            depth += 1;
+            i += 2;
+        } else if rest.starts_with("*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    code.len()
+}
+
+/// Length of `#[...]`, brackets inside included.
+fn attribute(code: &str) -> usize {
+    let mut depth = 0;
+    for (i, c) in code.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            '\n' => return i,
+            _ => {}
+        }
+    }
+    code.len()
+}
+
+/// Length of the string literal at the start of `code`, if there is one:
+/// `"..."`, `b"..."`, or raw `r#"..."#` with any number of `#`.
+fn string(code: &str) -> Option<usize> {
+    let prefix = ["br", "r", "b", ""].into_iter().find(|p| {
+        let Some(rest) = code.strip_prefix(p) else {
+            return false;
+        };
+        rest.starts_with('"') || (p.ends_with('r') && rest.starts_with('#'))
+    })?;
+    let rest = &code[prefix.len()..];
+
+    if prefix.ends_with('r') {
+        let hashes = rest.len() - rest.trim_start_matches('#').len();
+        let body = rest[hashes..].strip_prefix('"')?;
+        let end = "\"".to_string() + &"#".repeat(hashes);
+        let len = body.find(&end).map_or(body.len(), |i| i + end.len());
+        return Some(prefix.len() + hashes + 1 + len);
+    }
+
+    let mut escaped = false;
+    for (i, c) in rest.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(prefix.len() + i + 1),
+            _ => {}
+        }
+    }
+    Some(code.len())
+}
+
+/// Length of a character literal such as `'a'` or `'\n'` at the start of
+/// `code`, or `None` for a lifetime.
+fn char_literal(code: &str) -> Option<usize> {
+    let body = &code[1..];
+    if let Some(escape) = body.strip_prefix('\\') {
+        let close = escape.find('\'')?;
+        return Some(1 + 1 + close + 1);
+    }
+    let c = body.chars().next()?;
+    body[c.len_utf8()..].starts_with('\'').then(|| 1 + c.len_utf8() + 1)
+}
+
+fn number(code: &str) -> usize {
+    let mut len = 0;
+    let mut chars = code.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let fraction = c == '.' && chars.peek().is_some_and(|(_, next)| next.is_ascii_digit());
+        if !(c.is_ascii_alphanumeric() || c == '_' || fraction) {
+            break;
+        }
+        len = i + c.len_utf8();
+    }
+    len
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::quiz_bank;
+
+    fn kinds(code: &str) -> Vec<(Kind, &str)> {
+        tokenize(code)
+            .into_iter()
+            .filter(|t| !t.text.trim().is_empty())
+            .map(|t| (t.kind, t.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_classifies_tokens() {
+        assert_eq!(
+            kinds("fn greet(name: String) { println!(\"hi {}\", name); }"),
+            vec![
+                (Kind::Keyword, "fn"),
+                (Kind::Function, "greet"),
+                (Kind::Plain, "(name: "),
+                (Kind::Type, "String"),
+                (Kind::Plain, ") { "),
+                (Kind::Macro, "println!"),
+                (Kind::Plain, "("),
+                (Kind::String, "\"hi {}\""),
+                (Kind::Plain, ", name); }"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_literals_comments_and_lifetimes() {
+        assert_eq!(
+            kinds("let c = '\\n'; // note\nfn f<'a>(x: &'a u8) -> f64 { 1.5e3 }"),
+            vec![
+                (Kind::Keyword, "let"),
+                (Kind::Plain, " c = "),
+                (Kind::String, "'\\n'"),
+                (Kind::Plain, "; "),
+                (Kind::Comment, "// note"),
+                (Kind::Keyword, "fn"),
+                (Kind::Plain, " f<"),
+                (Kind::Lifetime, "'a"),
+                (Kind::Plain, ">(x: &"),
+                (Kind::Lifetime, "'a"),
+                (Kind::Type, "u8"),
+                (Kind::Plain, ") -> "),
+                (Kind::Type, "f64"),
+                (Kind::Plain, " { "),
+                (Kind::Number, "1.5e3"),
+                (Kind::Plain, " }"),
+            ]
+        );
+        assert_eq!(kinds("r#\"a \" b\"#"), vec![(Kind::String, "r#\"a \" b\"#")]);
+        assert_eq!(kinds("#[derive(Debug)]"), vec![(Kind::Attribute, "#[derive(Debug)]")]);
+        assert_eq!(kinds("let ____ = 1;")[1], (Kind::Blank, BLANK));
+    }
+
+    #[test]
+    fn test_lines_split_tokens() {
+        let lines = lines("/* a\nb */ x\n\ny\n");
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], vec![Token { kind: Kind::Comment, text: "/* a" }]);
+        assert_eq!(lines[1][0], Token { kind: Kind::Comment, text: "b */" });
+        assert!(lines[2].is_empty());
+        assert_eq!(lines[3], vec![Token { kind: Kind::Plain, text: "y" }]);
+    }
+
+    #[test]
+    fn test_every_snippet_round_trips() {
+        for quiz in quiz_bank() {
+            let Some(code) = &quiz.code else { continue };
+            let joined: String = tokenize(code).iter().map(|t| t.text).collect();
+            assert_eq!(&joined, code, "question {}", quiz.id);
+            assert_eq!(lines(code).len(), code.lines().count(), "question {}", quiz.id);
+        }
+    }
+}
@@ -1,2 +1,14 @@
 pub mod quiz;
-pub mod engine;
\ No newline at end of file
+pub mod engine;
+pub mod loader;
+pub mod validate;
+pub mod random;
+pub mod select;
+pub mod session;
+pub mod scoring;
+pub mod schedule;
+pub mod adaptive;
+pub mod stats;
+pub mod highlight;
+pub mod protocol;
+pub mod gate;
@@ -0,0 +1,259 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::quiz::{quiz_bank, Quiz};
+
+/// On-disk layout of a question pack, shared by the TOML and JSON formats.
+///
+/// In TOML every question is a `[[quiz]]` table, in JSON the questions live
+/// in a top-level `"quiz"` array.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Pack {
+    #[serde(default)]
+    pub quiz: Vec<Quiz>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Toml,
+    Json,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "toml" => Some(Format::Toml),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "toml" => Ok(Format::Toml),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {:?} (expected toml or json)", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io { path: PathBuf, source: std::io::Error },
+    UnknownFormat(PathBuf),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io { path, source } => {
+                write!(f, "cannot read {}: {}", path.display(), source)
+            }
+            LoadError::UnknownFormat(path) => write!(
+                f,
+                "cannot tell the format of {} (expected a .toml or .json file)",
+                path.display()
+            ),
+            LoadError::Toml(err) => write!(f, "invalid TOML question pack: {}", err),
+            LoadError::Json(err) => write!(f, "invalid JSON question pack: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io { source, .. } => Some(source),
+            LoadError::UnknownFormat(_) => None,
+            LoadError::Toml(err) => Some(err),
+            LoadError::Json(err) => Some(err),
+        }
+    }
+}
+
+/// The questions compiled into the crate.
+pub fn default_pack() -> Vec<Quiz> {
+    quiz_bank()
+}
+
+pub fn parse_pack(text: &str, format: Format) -> Result<Vec<Quiz>, LoadError> {
+    let pack: Pack = match format {
+        Format::Toml => toml::from_str(text).map_err(LoadError::Toml)?,
+        Format::Json => serde_json::from_str(text).map_err(LoadError::Json)?,
+    };
+    Ok(pack.quiz)
+}
+
+/// Writes `quizzes` as a pack that [`parse_pack`] reads back.
+pub fn pack_to_string(quizzes: &[Quiz], format: Format) -> String {
+    let pack = Pack { quiz: quizzes.to_vec() };
+    match format {
+        Format::Toml => toml::to_string(&pack).expect("questions always serialize to TOML"),
+        Format::Json => serde_json::to_string_pretty(&pack).expect("questions always serialize to JSON"),
+    }
+}
+
+/// Reads a single pack, picking the format from the file extension.
+pub fn load_pack(path: impl AsRef<Path>) -> Result<Vec<Quiz>, LoadError> {
+    let path = path.as_ref();
+    let format = Format::from_path(path).ok_or_else(|| LoadError::UnknownFormat(path.to_path_buf()))?;
+    let text = fs::read_to_string(path).map_err(|source| LoadError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse_pack(&text, format)
+}
+
+/// The default pack followed by every external pack, in the given order.
+pub fn load_bank<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<Quiz>, LoadError> {
+    let mut quizzes = default_pack();
+    for path in paths {
+        quizzes.extend(load_pack(path)?);
+    }
+    Ok(quizzes)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::{Answer, Expect, Level, Matching, TextAnswer};
+    use std::borrow::Cow;
+
+    const TOML_PACK: &str = r#"
+[[quiz]]
+id = 101
+title = "Unit type"
+question = "What is the type of `()`?"
+choices = ["never", "unit", "empty"]
+correct = 1
+explanation = "`()` is the unit type."
+tags = ["types"]
+level = "beginner_plus"
+"#;
+
+    const JSON_PACK: &str = r#"{
+  "quiz": [
+    {
+      "id": 102,
+      "title": "Shadowing",
+      "question": "What does this print?",
+      "code": "let x = 1;\nlet x = x + 1;\nprintln!(\"{x}\");",
+      "choices": ["1", "2"],
+      "correct": 1,
+      "explanation": "The second `x` shadows the first.",
+      "level": "intro"
+    }
+  ]
+}"#;
+
+    #[test]
+    fn test_parse_toml_pack() {
+        let quizzes = parse_pack(TOML_PACK, Format::Toml).unwrap();
+
+        assert_eq!(quizzes.len(), 1);
+        assert_eq!(quizzes[0].id, 101);
+        assert_eq!(quizzes[0].correct, Answer::Single(1));
+        assert_eq!(quizzes[0].level, Level::BeginnerPlus);
+        assert!(quizzes[0].code.is_none());
+    }
+
+    #[test]
+    fn test_parse_json_pack() {
+        let quizzes = parse_pack(JSON_PACK, Format::Json).unwrap();
+
+        assert_eq!(quizzes.len(), 1);
+        assert_eq!(quizzes[0].code.as_deref(), Some("let x = 1;\nlet x = x + 1;\nprintln!(\"{x}\");"));
+        assert!(quizzes[0].tags.is_empty());
+        assert_eq!(quizzes[0].level, Level::Intro);
+    }
+
+    #[test]
+    fn test_parse_multiple_answer() {
+        let text = TOML_PACK.replace("correct = 1", "correct = [0, 1]");
+        let quizzes = parse_pack(&text, Format::Toml).unwrap();
+
+        assert_eq!(quizzes[0].correct, Answer::Multiple(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_parse_text_answer() {
+        let text = TOML_PACK.replace(
+            "correct = 1",
+            "correct = { accepted = [\"unit\", \"()\"], matching = \"case_insensitive\" }",
+        );
+        let quizzes = parse_pack(&text, Format::Toml).unwrap();
+
+        assert_eq!(
+            quizzes[0].correct,
+            Answer::Text(TextAnswer {
+                accepted: vec!["unit".into(), "()".into()],
+                matching: Matching::CaseInsensitive,
+            })
+        );
+    }
+
+    #[test]
+    fn test_example_pack_parses() {
+        let quizzes = parse_pack(include_str!("../packs/example.toml"), Format::Toml).unwrap();
+
+        assert_eq!(quizzes.len(), 2);
+        assert!(quizzes[1].code.is_some());
+        assert_eq!(quizzes[1].expect, Some(Expect::Stdout("4".into())));
+    }
+
+    #[test]
+    fn test_invalid_pack_is_an_error() {
+        let result = parse_pack("[[quiz]]\nid = 1", Format::Toml);
+
+        assert!(matches!(result, Err(LoadError::Toml(_))));
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(Format::from_path(Path::new("extra.toml")), Some(Format::Toml));
+        assert_eq!(Format::from_path(Path::new("extra.JSON")), Some(Format::Json));
+        assert_eq!(Format::from_path(Path::new("extra.yaml")), None);
+    }
+
+    #[test]
+    fn test_default_pack_borrows_its_text() {
+        for quiz in default_pack() {
+            assert!(matches!(quiz.question, Cow::Borrowed(_)), "quiz {} allocates", quiz.id);
+            assert!(quiz.choices.iter().all(|c| matches!(c, Cow::Borrowed(_))));
+        }
+    }
+
+    #[test]
+    fn test_default_pack_round_trips_through_toml() {
+        let pack = Pack { quiz: default_pack() };
+        let text = toml::to_string(&pack).unwrap();
+        let quizzes = parse_pack(&text, Format::Toml).unwrap();
+
+        assert_eq!(quizzes.len(), pack.quiz.len());
+        assert_eq!(quizzes[0].title, pack.quiz[0].title);
+        assert_eq!(quizzes[3].expect, pack.quiz[3].expect);
+    }
+
+    #[test]
+    fn test_pack_to_string_round_trips() {
+        for format in ["toml", "json"] {
+            let format: Format = format.parse().unwrap();
+            let text = pack_to_string(&default_pack()[..3], format);
+
+            let ids: Vec<u32> = parse_pack(&text, format).unwrap().iter().map(|q| q.id).collect();
+            assert_eq!(ids, vec![1, 2, 3]);
+        }
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,291 @@
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{describe_solution, AnswerResult, Attempt, Clock, QuizState, Response};
+use crate::gate::Verdict;
+use crate::quiz::{Answer, Quiz};
+use crate::scoring::Scoring;
+
+/// Which [`Response`] a question takes, named like its variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnswerKind {
+    Choice,
+    Choices,
+    Text,
+    Order,
+    Blanks,
+}
+
+impl AnswerKind {
+    pub fn of(answer: &Answer) -> Self {
+        match answer {
+            Answer::Single(_) => AnswerKind::Choice,
+            Answer::Multiple(_) => AnswerKind::Choices,
+            Answer::Text(_) => AnswerKind::Text,
+            Answer::Order { .. } => AnswerKind::Order,
+            Answer::Blanks { .. } => AnswerKind::Blanks,
+        }
+    }
+
+    pub fn of_response(response: &Response) -> Self {
+        match response {
+            Response::Choice(_) => AnswerKind::Choice,
+            Response::Choices(_) => AnswerKind::Choices,
+            Response::Text(_) => AnswerKind::Text,
+            Response::Order(_) => AnswerKind::Order,
+            Response::Blanks(_) => AnswerKind::Blanks,
+        }
+    }
+
+    /// The name used in JSON.
+    pub fn name(self) -> &'static str {
+        match self {
+            AnswerKind::Choice => "choice",
+            AnswerKind::Choices => "choices",
+            AnswerKind::Text => "text",
+            AnswerKind::Order => "order",
+            AnswerKind::Blanks => "blanks",
+        }
+    }
+}
+
+/// A choice as shown, with the index responses use for it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Choice {
+    pub index: usize,
+    pub text: String,
+}
+
+/// A message from the quiz, written as one line of JSON.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// The next question, waiting for an `answer` command.
+    Question {
+        /// Position in the quiz, from 1.
+        number: usize,
+        total: usize,
+        id: u32,
+        title: String,
+        question: String,
+        code: Option<String>,
+        answer: AnswerKind,
+        /// In the order shown.
+        choices: Vec<Choice>,
+        blanks: usize,
+        time_left_ms: Option<u64>,
+    },
+    /// How the last answer was graded.
+    Feedback {
+        id: u32,
+        result: AnswerResult,
+        earned: f64,
+        timed_out: bool,
+        solution: String,
+        explanation: String,
+        /// Anything else worth telling, such as a level change.
+        note: Option<String>,
+    },
+    /// A command that could not be used. The question is still waiting.
+    Error { message: String },
+    /// The quiz is over.
+    Result {
+        score: usize,
+        total: usize,
+        points: f64,
+        max_points: f64,
+        percent: f64,
+        scoring: String,
+        answers: Vec<Attempt>,
+    },
+    /// Whether the quiz met its pass marks, after the result.
+    Verdict(Verdict),
+}
+
+impl Event {
+    /// The current question of `state`, out of `total`.
+    pub fn question(quiz: &Quiz, state: &QuizState, total: usize, clock: &impl Clock) -> Self {
+        Event::Question {
+            number: state.current + 1,
+            total,
+            id: quiz.id,
+            title: quiz.title.to_string(),
+            question: quiz.question.to_string(),
+            code: quiz.code.as_deref().map(str::to_string),
+            answer: AnswerKind::of(&quiz.correct),
+            choices: state
+                .presented_choices(quiz)
+                .into_iter()
+                .map(|(index, text)| Choice { index, text: text.to_string() })
+                .collect(),
+            blanks: quiz.blank_count(),
+            time_left_ms: state.time_left(clock).map(|left| left.as_millis() as u64),
+        }
+    }
+
+    /// The feedback on the answer just submitted to `quiz`, if there is one.
+    pub fn feedback(quiz: &Quiz, state: &QuizState, note: Option<String>) -> Option<Self> {
+        let feedback = state.feedback.as_ref()?;
+        Some(Event::Feedback {
+            id: quiz.id,
+            result: feedback.result,
+            earned: feedback.earned,
+            timed_out: feedback.timed_out,
+            solution: describe_solution(quiz),
+            explanation: quiz.explanation.to_string(),
+            note,
+        })
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Event::Error { message: message.into() }
+    }
+
+    /// The outcome of a finished quiz, with every answer.
+    pub fn result(quizzes: &[Quiz], state: &QuizState, scoring: Scoring) -> Self {
+        let report = state.report(quizzes, scoring.strategy().as_ref());
+        Event::Result {
+            score: state.score,
+            total: quizzes.len(),
+            points: report.points,
+            max_points: report.max_points,
+            percent: report.percent(),
+            scoring: scoring.name().to_string(),
+            answers: state.history.clone(),
+        }
+    }
+
+    /// One line of JSON, without the line break.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("an event is always valid JSON")
+    }
+
+    pub fn from_json(line: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(line)
+    }
+}
+
+/// A message to the quiz, read as one line of JSON.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    /// Submits an answer to the waiting question, for example
+    /// `{"command": "answer", "response": {"choice": 2}}`.
+    Answer { response: Response },
+    /// Stops the quiz.
+    Quit,
+}
+
+impl Command {
+    pub fn from_json(line: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(line)
+    }
+}
+
+/// Checks that `response` is the kind `quiz` takes and that its choice
+/// indices exist, so a mistake in the client is reported rather than graded
+/// as a wrong answer.
+pub fn check_response(quiz: &Quiz, response: &Response) -> Result<(), String> {
+    let expected = AnswerKind::of(&quiz.correct);
+    let given = AnswerKind::of_response(response);
+    if given != expected {
+        return Err(format!(
+            "question {} takes a {:?} response, not {:?}",
+            quiz.id,
+            expected.name(),
+            given.name()
+        ));
+    }
+
+    let count = quiz.choices.len();
+    let indices: &[usize] = match response {
+        Response::Choice(choice) => std::slice::from_ref(choice),
+        Response::Choices(chosen) | Response::Order(chosen) => chosen,
+        Response::Text(_) | Response::Blanks(_) => &[],
+    };
+    if let Some(missing) = indices.iter().find(|&&i| i >= count) {
+        return Err(format!("there is no choice {}", missing));
+    }
+
+    match response {
+        Response::Choices(chosen) if chosen.is_empty() => Err("choose at least one choice".to_string()),
+        Response::Order(order) => {
+            let mut placed = order.clone();
+            placed.sort_unstable();
+            placed.dedup();
+            if placed.len() == count && order.len() == count {
+                Ok(())
+            } else {
+                Err(format!("an order must list each of the {} lines once", count))
+            }
+        }
+        Response::Text(text) if text.trim().is_empty() => Err("the answer is empty".to_string()),
+        Response::Blanks(filled) if filled.len() != quiz.blank_count() => {
+            Err(format!("expected {} blanks, got {}", quiz.blank_count(), filled.len()))
+        }
+        _ => Ok(()),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::quiz_bank;
+
+    fn question(id: u32) -> Quiz {
+        quiz_bank().into_iter().find(|q| q.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_question_event_lists_choices_as_shown() {
+        let quiz = question(1);
+        let state = QuizState::new().with_shuffled_choices(7);
+        let event = Event::question(&quiz, &state, 3, &|| 0);
+
+        let Event::Question { number, total, answer, choices, .. } = &event else { panic!("expected a question") };
+        assert_eq!((*number, *total, *answer), (1, 3, AnswerKind::Choice));
+        let shown: Vec<usize> = choices.iter().map(|c| c.index).collect();
+        assert_eq!(shown, state.choice_order(&quiz));
+        assert!(event.to_json().starts_with(r#"{"event":"question","number":1"#));
+    }
+
+    #[test]
+    fn test_commands_from_json() {
+        assert_eq!(
+            Command::from_json(r#"{"command": "answer", "response": {"choices": [0, 2]}}"#).unwrap(),
+            Command::Answer { response: Response::Choices(vec![0, 2]) }
+        );
+        assert_eq!(Command::from_json(r#"{"command": "quit"}"#).unwrap(), Command::Quit);
+        assert!(Command::from_json(r#"{"command": "answer"}"#).is_err());
+    }
+
+    #[test]
+    fn test_check_response() {
+        let single = question(1);
+        assert_eq!(check_response(&single, &Response::Choice(3)), Ok(()));
+        assert!(check_response(&single, &Response::Choice(4)).is_err());
+        assert!(check_response(&single, &Response::Text("rs".into())).is_err());
+
+        let order = question(23);
+        assert_eq!(check_response(&order, &Response::Order(vec![1, 3, 0, 2])), Ok(()));
+        assert!(check_response(&order, &Response::Order(vec![1, 1, 0, 2])).is_err());
+
+        let blanks = question(24);
+        assert!(check_response(&blanks, &Response::Blanks(vec![])).is_err());
+    }
+
+    #[test]
+    fn test_result_event_has_every_answer() {
+        let quizzes: Vec<Quiz> = quiz_bank().into_iter().take(2).collect();
+        let mut state = QuizState::new();
+        state.submit(&quizzes, Response::Choice(quizzes[0].correct.correct_choices()[0]));
+        state.submit(&quizzes, Response::Choice(usize::MAX));
+
+        let Event::Result { score, total, answers, scoring, .. } = Event::result(&quizzes, &state, Scoring::Plain) else {
+            panic!("expected a result");
+        };
+        assert_eq!((score, total, scoring.as_str()), (1, 2, "plain"));
+        assert_eq!(answers, state.history);
+    }
+}
@@ -1,5 +1,12 @@
 #![allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Level {
     Intro,
     Beginner,
@@ -7,214 +14,372 @@ pub enum Level {
     Intermediate,
 }
 
-#[derive(Clone, Debug)]
+impl Level {
+    /// Every level, easiest first.
+    pub const ALL: [Level; 4] = [Level::Intro, Level::Beginner, Level::BeginnerPlus, Level::Intermediate];
+
+    /// The name used in packs and on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Intro => "intro",
+            Level::Beginner => "beginner",
+            Level::BeginnerPlus => "beginner_plus",
+            Level::Intermediate => "intermediate",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Level::Intro => "Intro",
+            Level::Beginner => "Beginner",
+            Level::BeginnerPlus => "Beginner+",
+            Level::Intermediate => "Intermediate",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let wanted = s.trim().to_ascii_lowercase().replace(['-', '+'], "_");
+        let wanted = if wanted == "beginner_" { "beginner_plus".to_string() } else { wanted };
+        Level::ALL
+            .into_iter()
+            .find(|level| level.name() == wanted)
+            .ok_or_else(|| format!("unknown level {:?} (expected intro, beginner, beginner_plus or intermediate)", s))
+    }
+}
+
+/// The outcome a question claims for its code snippet when it is compiled
+/// (wrapped in `fn main` unless it defines one) and run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Expect {
+    /// Compiles and exits successfully.
+    Runs,
+    /// Compiles, exits successfully and prints exactly this (ignoring
+    /// surrounding whitespace).
+    Stdout(Text),
+    /// Compiles, exits successfully and writes lines containing each of
+    /// these to standard error, as `dbg!` and `eprintln!` do.
+    Stderr(Vec<Text>),
+    /// Compiles but panics at runtime.
+    Panics,
+    /// Fails to compile with a diagnostic containing this text, usually an
+    /// error code such as `E0382`.
+    CompileError(Text),
+}
+
+/// How typed answers are compared with `TextAnswer::accepted`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Matching {
+    /// Must be identical, apart from leading and trailing whitespace.
+    Exact,
+    /// Runs of whitespace count as a single space.
+    #[default]
+    Normalized,
+    /// Like `Normalized`, ignoring case.
+    CaseInsensitive,
+    /// Each accepted answer is a regular expression that must match the
+    /// whole trimmed input.
+    Regex,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextAnswer {
+    pub accepted: Vec<Text>,
+    #[serde(default)]
+    pub matching: Matching,
+}
+
+/// The right answer to a question.
+///
+/// In packs a single choice is written as its index (`correct = 2`), a
+/// "select all that apply" answer as a list of indices (`correct = [0, 2]`)
+/// and a typed answer as a table
+/// (`correct = { accepted = ["12"], matching = "normalized" }`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Single(usize),
+    Multiple(Vec<usize>),
+    Text(TextAnswer),
+    /// `choices` are lines of code shown out of order; `order` lists them
+    /// in the order that makes a valid program (`correct = { order = [1, 0] }`).
+    Order { order: Vec<usize> },
+    /// `code` contains one `BLANK` placeholder per entry, filled in left to
+    /// right (`correct = { blanks = [{ accepted = ["mut"] }] }`).
+    Blanks { blanks: Vec<TextAnswer> },
+}
+
+impl Answer {
+    /// Indices of the correct choices; empty unless the learner picks from
+    /// `Quiz::choices`.
+    pub fn correct_choices(&self) -> &[usize] {
+        match self {
+            Answer::Single(correct) => std::slice::from_ref(correct),
+            Answer::Multiple(correct) => correct,
+            Answer::Text(_) | Answer::Order { .. } | Answer::Blanks { .. } => &[],
+        }
+    }
+
+    pub fn is_correct_choice(&self, choice: usize) -> bool {
+        self.correct_choices().contains(&choice)
+    }
+
+    pub fn is_multiple(&self) -> bool {
+        matches!(self, Answer::Multiple(_))
+    }
+
+    /// Whether the learner picks from `Quiz::choices` rather than typing.
+    pub fn uses_choices(&self) -> bool {
+        matches!(self, Answer::Single(_) | Answer::Multiple(_))
+    }
+}
+
+/// Placeholder marking a gap in `Quiz::code` for `Answer::Blanks`.
+pub const BLANK: &str = "____";
+
+/// Text of a question. The built-in bank borrows string literals, while
+/// questions built at runtime (packs, user content) own their text.
+pub type Text = Cow<'static, str>;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Quiz {
     pub id: u32,
-    pub title: &'static str,
-    pub question: &'static str,
-    pub code: Option<&'static str>,
-    pub choices: Vec<&'static str>,
-    pub correct: usize,
-    pub explanation: &'static str,
-    pub tags: Vec<&'static str>,
+    pub title: Text,
+    pub question: Text,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Text>,
+    pub choices: Vec<Text>,
+    pub correct: Answer,
+    pub explanation: Text,
+    #[serde(default)]
+    pub tags: Vec<Text>,
     pub level: Level,
+    /// What the compiler does with `code`, checked by the `testing` harness.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect: Option<Expect>,
+}
+
+impl Quiz {
+    /// Number of `BLANK` placeholders in the code.
+    pub fn blank_count(&self) -> usize {
+        self.code.as_deref().map_or(0, |code| code.matches(BLANK).count())
+    }
 }
 
 pub fn quiz_bank() -> Vec<Quiz> {
     vec![
         Quiz {
             id: 1,
-            title: "Rust source file extension",
-            question: "Which file extension is used for a Rust source code file?",
+            title: "Rust source file extension".into(),
+            question: "Which file extension is used for a Rust source code file?".into(),
             code: None,
             choices: vec![
-                ".rt",
-                ".rust",
-                ".rst",
-                ".rs",
+                ".rt".into(),
+                ".rust".into(),
+                ".rst".into(),
+                ".rs".into(),
             ],
-            correct: 3,
-            explanation: "In rust we use \".rs\" when creating a source code file (such as \"main.rs\").",
-            tags: vec!["basics", "files"],
+            correct: Answer::Single(3),
+            explanation: "In rust we use \".rs\" when creating a source code file (such as \"main.rs\").".into(),
+            tags: vec!["basics".into(), "files".into()],
             level: Level::Intro,
+            expect: None,
         },
 
         Quiz {
             id: 2,
-            title: "Mutable variables in Rust",
-            question: "Which keyword is used to make a variable mutable in Rust?",
+            title: "Mutable variables in Rust".into(),
+            question: "Which keyword is used to make a variable mutable in Rust?".into(),
             code: None,
             choices: vec![
-                "&",
-                "let",
-                "const",
-                "mut",
+                "&".into(),
+                "let".into(),
+                "const".into(),
+                "mut".into(),
             ],
-            correct: 3,
-            explanation: "In Rust, variables are immutable by default. To make a variable mutable, you must use the `mut` keyword, for example: `let mut x = 5;`.",
-            tags: vec!["basics", "mutability"],
+            correct: Answer::Single(3),
+            explanation: "In Rust, variables are immutable by default. To make a variable mutable, you must use the `mut` keyword, for example: `let mut x = 5;`.".into(),
+            tags: vec!["basics".into(), "mutability".into()],
             level: Level::Intro,
+            expect: None,
         },
         Quiz {
             id: 3,
-            title: "String Length and Shadowing",
-            question: "What will the following code output in Rust?",
+            title: "String Length and Shadowing".into(),
+            question: "What will the following code output in Rust?".into(),
             code: Some(
-                "let chars = \"I love Rust!\";\nlet chars = chars.len();\nprintln!(\"Total chars: {}\", chars);"
+                "let chars = \"I love Rust!\";\nlet chars = chars.len();\nprintln!(\"Total chars: {}\", chars);".into()
             ),
             choices: vec![
-                "Total chars: 10",
-                "Total chars: 12",
-                "error[E0308]: mismatched types",
-                "error: argument never used",
+                "Total chars: 10".into(),
+                "Total chars: 12".into(),
+                "error[E0308]: mismatched types".into(),
+                "error: argument never used".into(),
             ],
-            correct: 1,
-            explanation: "Thanks to variable name shadowing, we could use the exact same variable name with a different datatype, which can be very convenient in certain contexts.",
-            tags: vec!["shadowing", "string", "len", "basics"],
+            correct: Answer::Single(1),
+            explanation: "Thanks to variable name shadowing, we could use the exact same variable name with a different datatype, which can be very convenient in certain contexts.".into(),
+            tags: vec!["shadowing".into(), "string".into(), "len".into(), "basics".into()],
             level: Level::Beginner,
+            expect: Some(Expect::Stdout("Total chars: 12".into())),
         },
         Quiz {
             id: 4,
-            title: "Integer Range Limits (i8)",
-            question: "What will the following code output in Rust?",
+            title: "Integer Range Limits (i8)".into(),
+            question: "What will the following code output in Rust?".into(),
             code: Some(
-                "let n: i8 = 1_000;\nprintln!(\"n={}\", n);"
+                "let n: i8 = 1_000;\nprintln!(\"n={}\", n);".into()
             ),
             choices: vec![
-                "n=1000",
-                "n=1_000",
-                "error: literal out of range for `i8`", // Correct choice
-                "None of the above",
+                "n=1000".into(),
+                "n=1_000".into(),
+                "error: literal out of range for `i8`".into(), // Correct choice
+                "None of the above".into(),
             ],
-            correct: 2, // Index of "error: literal out of range for `i8`"
-            explanation: "Since we told Rust that we want \"n\" to be of only 8 bits, \"n\" can only contain a value between -128 to 127. If we want a bigger value we're going to have to use i16.",
-            tags: vec!["integers", "i8", "data types", "range"],
+            correct: Answer::Single(2), // Index of "error: literal out of range for `i8`"
+            explanation: "Since we told Rust that we want \"n\" to be of only 8 bits, \"n\" can only contain a value between -128 to 127. If we want a bigger value we're going to have to use i16.".into(),
+            tags: vec!["integers".into(), "i8".into(), "data types".into(), "range".into()],
             level: Level::Beginner,
+            expect: Some(Expect::CompileError("literal out of range for `i8`".into())),
         },
         Quiz {
             id: 5,
-            title: "Default Integer Type Inference",
-            question: "What is the default type of \"x\" in Rust for:\n\nlet x = 5;",
-            code: Some("let x = 5;"),
+            title: "Default Integer Type Inference".into(),
+            question: "What is the default type of \"x\" in Rust for:\n\nlet x = 5;".into(),
+            code: Some("let x = 5;".into()),
             choices: vec![
-                "i8",
-                "u8",
-                "i32", // Correct choice
-                "i64",
+                "i8".into(),
+                "u8".into(),
+                "i32".into(), // Correct choice
+                "i64".into(),
             ],
-            correct: 2, // Index of "i32"
-            explanation: "If you don't specify a type, Rust defaults to \"i32\" for integers.",
-            tags: vec!["integers", "data types", "type inference", "i32"],
+            correct: Answer::Single(2), // Index of "i32"
+            explanation: "If you don't specify a type, Rust defaults to \"i32\" for integers.".into(),
+            tags: vec!["integers".into(), "data types".into(), "type inference".into(), "i32".into()],
             level: Level::Beginner,
+            expect: Some(Expect::Runs),
         },
         Quiz {
             id: 6,
-            title: "Centering with Formatting Specifiers",
-            question: "What will the following code output in Rust?",
+            title: "Centering with Formatting Specifiers".into(),
+            question: "What will the following code output in Rust?".into(),
             code: Some(
-                "let var = \"Rust\";\nprintln!(\"{var:*^10}\");",
+                "let var = \"Rust\";\nprintln!(\"{var:*^10}\");".into(),
             ),
             choices: vec![
-                "^^^Rust^^^",
-                "***Rust***", // Correct choice in the image
-                "******Rust",
-                "Rust******",
+                "^^^Rust^^^".into(),
+                "***Rust***".into(), // Correct choice in the image
+                "******Rust".into(),
+                "Rust******".into(),
             ],
-            correct: 1, // Index of "***Rust***"
-            explanation: "Just like in Python, we can use this format specifier to tell Rust that we want to centre an element using \"^\" with the symbol \"*\" and that we want that to occupy a total of 10 spaces.",
-            tags: vec!["formatting", "println", "macros", "specifiers"],
+            correct: Answer::Single(1), // Index of "***Rust***"
+            explanation: "Just like in Python, we can use this format specifier to tell Rust that we want to centre an element using \"^\" with the symbol \"*\" and that we want that to occupy a total of 10 spaces.".into(),
+            tags: vec!["formatting".into(), "println".into(), "macros".into(), "specifiers".into()],
             level: Level::BeginnerPlus,
+            expect: Some(Expect::Stdout("***Rust***".into())),
         },
         Quiz {
             id: 7,
-            title: "Array Initialization and Debug Print",
-            question: "What will the following code output in Rust?",
+            title: "Array Initialization and Debug Print".into(),
+            question: "What will the following code output in Rust?".into(),
             code: Some(
-                "let unknown = [\"Rust\"; 3];\nprintln!(\"{:?}\", unknown);",
+                "let unknown = [\"Rust\"; 3];\nprintln!(\"{:?}\", unknown);".into(),
             ),
             choices: vec![
-                "\"t\"",
-                "(\"Rust\", \"Rust\", \"Rust\")",
-                "[\"Rust\", \"Rust\", \"Rust\"]", // Correct choice
-                "error[E0277]",
+                "\"t\"".into(),
+                "(\"Rust\", \"Rust\", \"Rust\")".into(),
+                "[\"Rust\", \"Rust\", \"Rust\"]".into(), // Correct choice
+                "error[E0277]".into(),
             ],
-            correct: 2, // Index of "[\"Rust\", \"Rust\", \"Rust\"]"
-            explanation: "In Rust you can use the following syntax to create an array with repeating elements: \"let arr = [element; n];\". To print an array we need to use the \"{:?}\" debug format specifier.",
-            tags: vec!["arrays", "debug", "formatting", "syntax"],
+            correct: Answer::Single(2), // Index of "[\"Rust\", \"Rust\", \"Rust\"]"
+            explanation: "In Rust you can use the following syntax to create an array with repeating elements: \"let arr = [element; n];\". To print an array we need to use the \"{:?}\" debug format specifier.".into(),
+            tags: vec!["arrays".into(), "debug".into(), "formatting".into(), "syntax".into()],
             level: Level::Beginner,
+            expect: Some(Expect::Stdout("[\"Rust\", \"Rust\", \"Rust\"]".into())),
         }, 
         Quiz {
             id: 8,
-            title: "If Expression Assignment",
-            question: "What will the following code output in Rust?",
+            title: "If Expression Assignment".into(),
+            question: "What will the following code output in Rust?".into(),
             code: Some(
-                "let input = \"Rust\";\nlet text = if input.len() == 4 {\"YES\"} else {\"NO\"};\nprintln!(\"{}\", text)"
+                "let input = \"Rust\";\nlet text = if input.len() == 4 {\"YES\"} else {\"NO\"};\nprintln!(\"{}\", text)".into()
             ),
             choices: vec![
-                "YES", // Correct choice
-                "NO",
-                "Rust",
-                "error: could not compile",
+                "YES".into(), // Correct choice
+                "NO".into(),
+                "Rust".into(),
+                "error: could not compile".into(),
             ],
-            correct: 0, // Index of "YES"
-            explanation: "In Rust we can create a \"let if\" expression using the following syntax: \"let var = if condition {value1} else {value2};\". If the condition evaluates to true, the first value will be assigned to the variable, else the second one will.",
-            tags: vec!["if", "expression", "control flow", "string", "len"],
+            correct: Answer::Single(0), // Index of "YES"
+            explanation: "In Rust we can create a \"let if\" expression using the following syntax: \"let var = if condition {value1} else {value2};\". If the condition evaluates to true, the first value will be assigned to the variable, else the second one will.".into(),
+            tags: vec!["if".into(), "expression".into(), "control flow".into(), "string".into(), "len".into()],
             level: Level::BeginnerPlus,
+            expect: Some(Expect::Stdout("YES".into())),
         },
         Quiz {
             id: 9,
-            title: "Function Definition and Return Type",
-            question: "What is the correct way to define a function that returns an integer in Rust?",
+            title: "Function Definition and Return Type".into(),
+            question: "What is the correct way to define a function that returns an integer in Rust?".into(),
             code: None,
             choices: vec![
-                "fn add() -> i32 { 5 }", // Correct choice
-                "function add() { return 5; }",
-                "def add() -> int { 5 }",
-                "fn add { 5 }",
+                "fn add() -> i32 { 5 }".into(), // Correct choice
+                "function add() { return 5; }".into(),
+                "def add() -> int { 5 }".into(),
+                "fn add { 5 }".into(),
             ],
-            correct: 0, 
-            explanation: "Rust functions are defined with \"fn\", and \"-> i32\" means the function returns a 32-bit integer.",
-            tags: vec!["function", "syntax", "i32", "return"],
+            correct: Answer::Single(0), 
+            explanation: "Rust functions are defined with \"fn\", and \"-> i32\" means the function returns a 32-bit integer.".into(),
+            tags: vec!["function".into(), "syntax".into(), "i32".into(), "return".into()],
             level: Level::Beginner,
+            expect: None,
         },
         Quiz {
             id: 10,
-            title: "Ownership and Move Semantics",
-            question: "What will the following code output in Rust?",
+            title: "Ownership and Move Semantics".into(),
+            question: "What will the following code output in Rust?".into(),
             code: Some(
-                "let original = String::from(\"Bob\");\nlet copy = original;\nprintln!(\"1. {}, 2. {}\", original, copy);"
+                "let original = String::from(\"Bob\");\nlet copy = original;\nprintln!(\"1. {}, 2. {}\", original, copy);".into()
             ),
             choices: vec![
-                "1. {original}, 2. {copy}",
-                "1. Bob, 2. Bob",
-                "1. original, 2. copy",
-                "Error", // Correct choice
+                "1. {original}, 2. {copy}".into(),
+                "1. Bob, 2. Bob".into(),
+                "1. original, 2. copy".into(),
+                "Error".into(), // Correct choice
             ],
-            correct: 3, 
-            explanation: "Running this code will give you the following error: \"error[E0382]: borrow of moved value: `original`\". I will cover this in detail in a future lesson, but for now, know that what we did here is called a 'move'. We moved the data from the variable \"original\" to another variable, misleadingly named \"copy.\" Rust invalidates the \"original\" variable after this operation to ensure memory safety, so \"original\" is no longer valid, and \"copy\" essentially replaces it.",
-            tags: vec!["ownership", "move", "string", "error"],
+            correct: Answer::Single(3), 
+            explanation: "Running this code will give you the following error: \"error[E0382]: borrow of moved value: `original`\". I will cover this in detail in a future lesson, but for now, know that what we did here is called a 'move'. We moved the data from the variable \"original\" to another variable, misleadingly named \"copy.\" Rust invalidates the \"original\" variable after this operation to ensure memory safety, so \"original\" is no longer valid, and \"copy\" essentially replaces it.".into(),
+            tags: vec!["ownership".into(), "move".into(), "string".into(), "error".into()],
             level: Level::Intermediate,
+            expect: Some(Expect::CompileError("E0382".into())),
         },
         Quiz {
             id: 11,
-            title: "String Length (Bytes vs. Characters)",
-            question: "What will the following code output in Rust?",
-            code: Some("println!(\"{}\", \"äöå\".len());"),
+            title: "String Length (Bytes vs. Characters)".into(),
+            question: "What will the following code output in Rust?".into(),
+            code: Some("println!(\"{}\", \"äöå\".len());".into()),
             choices: vec![
-                "3",
-                "5",
-                "6", // Correct choice
-                "Error",
+                "3".into(),
+                "5".into(),
+                "6".into(), // Correct choice
+                "Error".into(),
             ],
-            correct: 2, 
-            explanation: "\"len()\" in Rust returns the length in bytes, not of the total amount of characters. If you want the latter you'd have to use: \".chars().count()\". \"öäå\" are special characters that contain 2 bytes each, that's why we get 6 as a return.",
-            tags: vec!["string", "len", "unicode", "bytes"],
+            correct: Answer::Single(2), 
+            explanation: "\"len()\" in Rust returns the length in bytes, not of the total amount of characters. If you want the latter you'd have to use: \".chars().count()\". \"öäå\" are special characters that contain 2 bytes each, that's why we get 6 as a return.".into(),
+            tags: vec!["string".into(), "len".into(), "unicode".into(), "bytes".into()],
             level: Level::BeginnerPlus,
+            expect: Some(Expect::Stdout("6".into())),
         },
         Quiz {
             id: 12,
-            title: "Loop Expression Return Value",
-            question: "What will the following code output in Rust?",
+            title: "Loop Expression Return Value".into(),
+            question: "What will the following code output in Rust?".into(),
             code: Some(
                 "let mut count = 0;
 let result = loop {
@@ -223,39 +388,41 @@ let result = loop {
         break count * 2;
     }
 };
-println!(\"{}\", result);"
+println!(\"{}\", result);".into()
             ),
             choices: vec![
-                "6",
-                "8",
-                "10",
-                "12", // Correct choice
+                "6".into(),
+                "8".into(),
+                "10".into(),
+                "12".into(), // Correct choice
             ],
-            correct: 3, 
-            explanation: "We start the count at 0 and loop until \"count\" increments to 6. Once \"count\" reaches 6, it triggers our \"count > 5\" condition and breaks out of the loop. Since we included \"count * 2\" after the break statement, that value is assigned to \"result,\" which is why we end up with an output of 12.",
-            tags: vec!["loop", "expression", "break", "control flow"],
+            correct: Answer::Single(3), 
+            explanation: "We start the count at 0 and loop until \"count\" increments to 6. Once \"count\" reaches 6, it triggers our \"count > 5\" condition and breaks out of the loop. Since we included \"count * 2\" after the break statement, that value is assigned to \"result,\" which is why we end up with an output of 12.".into(),
+            tags: vec!["loop".into(), "expression".into(), "break".into(), "control flow".into()],
             level: Level::Intermediate,
+            expect: Some(Expect::Stdout("12".into())),
         },
         Quiz {
             id: 13,
-            title: "Custom Fill Character Formatting",
-            question: "What will the following code output in Rust?",
-            code: Some("let name = \"Bob\";\nprintln!(\"{name:~^9}\");"),
+            title: "Custom Fill Character Formatting".into(),
+            question: "What will the following code output in Rust?".into(),
+            code: Some("let name = \"Bob\";\nprintln!(\"{name:~^9}\");".into()),
             choices: vec![
-                "^^^Bob^^^",
-                "~~~Bob~~~", // Correct choice
-                "Bob~~~~~~",
-                "Bob^^^^^^",
+                "^^^Bob^^^".into(),
+                "~~~Bob~~~".into(), // Correct choice
+                "Bob~~~~~~".into(),
+                "Bob^^^^^^".into(),
             ],
-            correct: 1, 
-            explanation: "This is one of my favourite styling format specifiers that I use in Python (and I'm happy to see that Rust has it as well). Here's how it works: \"name\" is the variable we want to use. \"~\" is the fill character. \"^\" centres the variable; you can also left-align or right-align using \"<\" or \">\", respectively. \"9\" specifies the total width to reserve for the variable, including its own length.",
-            tags: vec!["formatting", "println", "specifiers", "fill"],
+            correct: Answer::Single(1), 
+            explanation: "This is one of my favourite styling format specifiers that I use in Python (and I'm happy to see that Rust has it as well). Here's how it works: \"name\" is the variable we want to use. \"~\" is the fill character. \"^\" centres the variable; you can also left-align or right-align using \"<\" or \">\", respectively. \"9\" specifies the total width to reserve for the variable, including its own length.".into(),
+            tags: vec!["formatting".into(), "println".into(), "specifiers".into(), "fill".into()],
             level: Level::BeginnerPlus,
+            expect: Some(Expect::Stdout("~~~Bob~~~".into())),
         },
         Quiz {
             id: 14,
-            title: "Ownership Move on Function Call",
-            question: "What will the following code output in Rust?",
+            title: "Ownership Move on Function Call".into(),
+            question: "What will the following code output in Rust?".into(),
             code: Some(
                 "fn main() {
 \tlet name = String::from(\"Bob\");
@@ -265,126 +432,224 @@ println!(\"{}\", result);"
 
 fn greet(name: String) {
 \tprintln!(\"Hello, {}!\", name);
-}"
+}".into()
             ),
             choices: vec![
-                "Hello, Bob! Hello, Bob!",
-                "Hello, BobBob!",
-                "Error", // Correct choice
-                "None of the above",
+                "Hello, Bob! Hello, Bob!".into(),
+                "Hello, BobBob!".into(),
+                "Error".into(), // Correct choice
+                "None of the above".into(),
             ],
-            correct: 2, 
-            explanation: "Running this code will give you the following error: \"error[E0382]: use of moved value: `name`\". The reason is because we created an owned string using the \"String::from\" syntax and that means that we need to be careful where we use it if we don't want to lose ownership. A better solution would be to define our function to accept a string slice: \"fn greet(name: &str)\". Then we would be forced to pass in \"&name\".",
-            tags: vec!["ownership", "move", "function", "string"],
+            correct: Answer::Single(2), 
+            explanation: "Running this code will give you the following error: \"error[E0382]: use of moved value: `name`\". The reason is because we created an owned string using the \"String::from\" syntax and that means that we need to be careful where we use it if we don't want to lose ownership. A better solution would be to define our function to accept a string slice: \"fn greet(name: &str)\". Then we would be forced to pass in \"&name\".".into(),
+            tags: vec!["ownership".into(), "move".into(), "function".into(), "string".into()],
             level: Level::Intermediate,
+            expect: Some(Expect::CompileError("E0382".into())),
         },
         Quiz {
             id: 15,
-            title: "Struct Instantiation",
-            question: "What would be the correct way to instantiate this struct in Rust?\n\nstruct Rectangle { width: u32, height: u32 }",
+            title: "Struct Instantiation".into(),
+            question: "What would be the correct way to instantiate this struct in Rust?\n\nstruct Rectangle { width: u32, height: u32 }".into(),
             code: Some(
                 "struct Rectangle {
     width: u32,
     height: u32,
-}"
+}".into()
             ),
             choices: vec![
-                "Rectangle(10, 20);",
-                "Rectangle { width: 10, height: 20 };", // Correct choice (assuming second option in image is the same as the third but selected)
-                "Rectangle(width:10, height:20);",
-                "Rectangle {10, 20};",
+                "Rectangle(10, 20);".into(),
+                "Rectangle { width: 10, height: 20 };".into(), // Correct choice (assuming second option in image is the same as the third but selected)
+                "Rectangle(width:10, height:20);".into(),
+                "Rectangle {10, 20};".into(),
             ],
-            correct: 1, // Index of the correct curly-brace syntax
-            explanation: "To create an instance of \"Rectangle\" we must provide the values for both the width and the height inside curly brackets using the field names and colons.",
-            tags: vec!["struct", "syntax", "instantiation"],
+            correct: Answer::Single(1), // Index of the correct curly-brace syntax
+            explanation: "To create an instance of \"Rectangle\" we must provide the values for both the width and the height inside curly brackets using the field names and colons.".into(),
+            tags: vec!["struct".into(), "syntax".into(), "instantiation".into()],
             level: Level::Beginner,
+            expect: Some(Expect::Runs),
         },
         Quiz {
             id: 16,
-            title: "Option::unwrap() on None",
-            question: "What will happen if we try to run this code in Rust?",
+            title: "Option::unwrap() on None".into(),
+            question: "What will happen if we try to run this code in Rust?".into(),
             code: Some(
-                "let value: Option<u8> = None;\nlet unwrapped = value.unwrap();"
+                "let value: Option<u8> = None;\nlet unwrapped = value.unwrap();".into()
             ),
             choices: vec![
-                "The program will print '0' to the console.",
-                "The program will print 'None' to the console.",
-                "The program will panic at runtime.", // Correct choice
-                "The program will compile and run successfully without output.",
+                "The program will print '0' to the console.".into(),
+                "The program will print 'None' to the console.".into(),
+                "The program will panic at runtime.".into(), // Correct choice
+                "The program will compile and run successfully without output.".into(),
             ],
-            correct: 2, 
-            explanation: "When you call \".unwrap()\" on an Option-type that is \"None\", Rust will panic and terminate the program. If you want to provide a default value when unwrapping an Option-type, I'd recommend using: \"value.unwrap_or(0);\". This will use the default value of \"0\" in the case where value ends up being \"None\".",
-            tags: vec!["option", "panic", "unwrap", "error handling"],
+            correct: Answer::Single(2), 
+            explanation: "When you call \".unwrap()\" on an Option-type that is \"None\", Rust will panic and terminate the program. If you want to provide a default value when unwrapping an Option-type, I'd recommend using: \"value.unwrap_or(0);\". This will use the default value of \"0\" in the case where value ends up being \"None\".".into(),
+            tags: vec!["option".into(), "panic".into(), "unwrap".into(), "error handling".into()],
             level: Level::BeginnerPlus,
+            expect: Some(Expect::Panics),
         },
         Quiz {
             id: 17,
-            title: "Advantage of Option<T>",
-            question: "In Rust, what is the main advantage of using `Option<T>` over using null values?",
+            title: "Advantage of Option<T>".into(),
+            question: "In Rust, what is the main advantage of using `Option<T>` over using null values?".into(),
             code: None,
             choices: vec![
-                "It makes your code run faster.",
-                "It eliminates null reference errors.", // Correct choice
-                "It automatically fills in missing values with zero.",
-                "It allows you to save on memory.",
+                "It makes your code run faster.".into(),
+                "It eliminates null reference errors.".into(), // Correct choice
+                "It automatically fills in missing values with zero.".into(),
+                "It allows you to save on memory.".into(),
             ],
-            correct: 1, 
-            explanation: "In Rust, you can't use a value that might be \"null\" without explicitly handling the possibility first. This makes it much harder to accidentally use a missing value, reducing the risk of runtime errors.",
-            tags: vec!["option", "null", "error handling", "safety"],
+            correct: Answer::Single(1), 
+            explanation: "In Rust, you can't use a value that might be \"null\" without explicitly handling the possibility first. This makes it much harder to accidentally use a missing value, reducing the risk of runtime errors.".into(),
+            tags: vec!["option".into(), "null".into(), "error handling".into(), "safety".into()],
             level: Level::BeginnerPlus,
+            expect: None,
         },
         Quiz {
             id: 18,
-            title: "Panic Macro Syntax",
-            question: "Which of these is the correct way to trigger a panic in Rust?",
+            title: "Panic Macro Syntax".into(),
+            question: "Which of these is the correct way to trigger a panic in Rust?".into(),
             code: None,
             choices: vec![
-                "panic(\"Something went wrong!\")",
-                "throw!(\"Something went wrong!\")",
-                "raise!(\"Something went wrong!\")",
-                "panic!(\"Something went wrong!\")", // Correct choice
+                "panic(\"Something went wrong!\")".into(),
+                "throw!(\"Something went wrong!\")".into(),
+                "raise!(\"Something went wrong!\")".into(),
+                "panic!(\"Something went wrong!\")".into(), // Correct choice
             ],
-            correct: 3, 
-            explanation: "Remember, \"panic!\" is a macro and requires the \"!\" to run.",
-            tags: vec!["panic", "macro", "syntax", "error handling"],
+            correct: Answer::Single(3), 
+            explanation: "Remember, \"panic!\" is a macro and requires the \"!\" to run.".into(),
+            tags: vec!["panic".into(), "macro".into(), "syntax".into(), "error handling".into()],
             level: Level::Beginner,
+            expect: None,
         },
         Quiz {
             id: 19,
-            title: "String Slicing (Byte Indexing)",
-            question: "Which of the following code snippets will allow you to access \"5\" from the following string in Rust?",
-            code: Some("let s = \"Bob 5\";"),
+            title: "String Slicing (Byte Indexing)".into(),
+            question: "Which of the following code snippets will allow you to access \"5\" from the following string in Rust?".into(),
+            code: Some("let s = \"Bob 5\";\nprintln!(\"{}\", &s[4..5]);".into()),
             choices: vec![
-                "&s[4..5]", // Correct choice
-                "&s[5]",
-                "&s[4]",
-                "&s.char_at(4)",
+                "&s[4..5]".into(), // Correct choice
+                "&s[5]".into(),
+                "&s[4]".into(),
+                "&s.char_at(4)".into(),
             ],
-            correct: 0, 
-            explanation: "In Rust, you can't index a string directly by character because strings are UTF-8 encoded and characters can be multiple bytes. However, you can slice a string using byte ranges — as long as those byte boundaries align with valid UTF-8 characters.",
-            tags: vec!["string", "slice", "indexing", "byte"],
+            correct: Answer::Single(0), 
+            explanation: "In Rust, you can't index a string directly by character because strings are UTF-8 encoded and characters can be multiple bytes. However, you can slice a string using byte ranges — as long as those byte boundaries align with valid UTF-8 characters.".into(),
+            tags: vec!["string".into(), "slice".into(), "indexing".into(), "byte".into()],
             level: Level::Intermediate,
+            expect: Some(Expect::Stdout("5".into())),
         },
         Quiz {
             id: 20,
-            title: "Integer Copy Trait",
-            question: "What will happen when we run this code in Rust?",
+            title: "Integer Copy Trait".into(),
+            question: "What will happen when we run this code in Rust?".into(),
             code: Some(
                 "let x = 42;
 let y = x;
-dbg!(x, y);"
+dbg!(x, y);".into()
             ),
             choices: vec![
-                "Only 'y' is printed, since 'x' is moved.",
-                "The program will panic.",
-                "Both 'x' and 'y' are displayed correctly using 'dbg!'", // Correct choice
-                "error[E0382]: use of moved value: 'x'",
+                "Only 'y' is printed, since 'x' is moved.".into(),
+                "The program will panic.".into(),
+                "Both 'x' and 'y' are displayed correctly using 'dbg!'".into(), // Correct choice
+                "error[E0382]: use of moved value: 'x'".into(),
+            ],
+            correct: Answer::Single(2), 
+            explanation: "In Rust, integers implement the `Copy` trait. When you assign `x` to `y`, the value is copied, not moved, so both `x` and `y` remain usable. The `dbg!()` macro prints both values along with file and line number information.".into(),
+            tags: vec!["copy".into(), "move".into(), "ownership".into(), "integer".into(), "trait".into()],
+            level: Level::Intermediate,
+            expect: Some(Expect::Stderr(vec!["x = 42".into(), "y = 42".into()])),
+        },
+        Quiz {
+            id: 21,
+            title: "Which Types Are Copy".into(),
+            question: "Which of these types implement the `Copy` trait? (Select all that apply)".into(),
+            code: None,
+            choices: vec![
+                "i32".into(), // Correct choice
+                "String".into(),
+                "&str".into(), // Correct choice
+                "Vec<u8>".into(),
+                "bool".into(), // Correct choice
             ],
-            correct: 2, 
-            explanation: "In Rust, integers implement the `Copy` trait. When you assign `x` to `y`, the value is copied, not moved, so both `x` and `y` remain usable. The `dbg!()` macro prints both values along with file and line number information.",
-            tags: vec!["copy", "move", "ownership", "integer", "trait"],
+            correct: Answer::Multiple(vec![0, 2, 4]),
+            explanation: "Scalar types such as `i32` and `bool` are `Copy`, and so are shared references like `&str`: copying them just copies the pointer. `String` and `Vec<u8>` own heap memory, so assigning them moves ownership instead.".into(),
+            tags: vec!["copy".into(), "move".into(), "ownership".into(), "trait".into()],
             level: Level::Intermediate,
+            expect: None,
+        },
+        Quiz {
+            id: 22,
+            title: "Summing an Iterator".into(),
+            question: "What will the following code print? Type the exact output.".into(),
+            code: Some(
+                "let v = vec![1, 2, 3];
+let total: i32 = v.iter().sum();
+println!(\"{}\", total * 2);".into()
+            ),
+            choices: vec![],
+            correct: Answer::Text(TextAnswer {
+                accepted: vec!["12".into()],
+                matching: Matching::Normalized,
+            }),
+            explanation: "`iter()` yields references to the elements, and `sum()` adds them up into the annotated type `i32`: 1 + 2 + 3 = 6. Doubling it prints 12.".into(),
+            tags: vec!["iterator".into(), "vec".into(), "sum".into()],
+            level: Level::BeginnerPlus,
+            expect: Some(Expect::Stdout("12".into())),
+        },
+        Quiz {
+            id: 23,
+            title: "Order the Program".into(),
+            question: "Put these lines in order so the program prints \"Hello, Ferris!\".".into(),
+            code: None,
+            choices: vec![
+                "    println!(\"Hello, {}!\", name);".into(),
+                "fn main() {".into(),
+                "}".into(),
+                "    let name = \"Ferris\";".into(),
+            ],
+            correct: Answer::Order { order: vec![1, 3, 0, 2] },
+            explanation: "Every Rust program starts running in `fn main`, and a variable has to be declared with `let` before it can be used, so the binding comes before the `println!` that reads it.".into(),
+            tags: vec!["function".into(), "syntax".into(), "basics".into()],
+            level: Level::Intro,
+            expect: None,
+        },
+        Quiz {
+            id: 24,
+            title: "Fill In the Missing Keyword".into(),
+            question: "Fill in the blank so that the code compiles and prints 1.".into(),
+            code: Some(
+                "let ____ count = 0;
+count += 1;
+println!(\"{}\", count);".into()
+            ),
+            choices: vec![],
+            correct: Answer::Blanks {
+                blanks: vec![TextAnswer {
+                    accepted: vec!["mut".into()],
+                    matching: Matching::Exact,
+                }],
+            },
+            explanation: "Variables are immutable by default, so `count += 1` only compiles if `count` is declared with `let mut`.".into(),
+            tags: vec!["mutability".into(), "basics".into()],
+            level: Level::Beginner,
+            expect: Some(Expect::Stdout("1".into())),
         }
             
     ]
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_names_match_packs() {
+        for level in Level::ALL {
+            assert_eq!(level.name().parse::<Level>(), Ok(level));
+            assert_eq!(toml::Value::try_from(level).unwrap().as_str(), Some(level.name()));
+        }
+        assert_eq!("Beginner+".parse::<Level>(), Ok(Level::BeginnerPlus));
+        assert!("expert".parse::<Level>().is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::AnswerResult;
+use crate::quiz::Quiz;
+
+const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// How many questions never seen before are mixed into a day's review.
+pub const NEW_PER_DAY: usize = 5;
+
+/// The day number (days since the Unix epoch) of a time in milliseconds.
+pub fn day(millis: u64) -> u64 {
+    millis / DAY_MILLIS
+}
+
+/// Spaced-repetition state for one question, updated with the SM-2 rules.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Card {
+    /// How quickly the interval grows; never below 1.3.
+    pub ease: f64,
+    /// Days until the next review after the last one.
+    pub interval: u64,
+    /// Correct reviews in a row.
+    pub repetitions: u32,
+    /// Day number the question is next due on.
+    pub due: u64,
+}
+
+impl Default for Card {
+    fn default() -> Self {
+        Self {
+            ease: 2.5,
+            interval: 0,
+            repetitions: 0,
+            due: 0,
+        }
+    }
+}
+
+impl Card {
+    /// Reschedules the card after answering it on day `today`.
+    ///
+    /// Results map to SM-2 grades: correct is 4, partial 3 and wrong 1. A
+    /// wrong answer starts the card over and brings it back tomorrow.
+    pub fn review(&mut self, result: AnswerResult, today: u64) {
+        let grade = match result {
+            AnswerResult::Correct => 4.0,
+            AnswerResult::Partial => 3.0,
+            AnswerResult::Wrong => 1.0,
+            AnswerResult::Finished => return,
+        };
+
+        if grade < 3.0 {
+            self.repetitions = 0;
+            self.interval = 1;
+        } else {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval as f64 * self.ease).round() as u64,
+            };
+            self.repetitions += 1;
+        }
+        let miss = 5.0 - grade;
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(1.3);
+        self.due = today + self.interval;
+    }
+}
+
+/// Review state for every question answered so far, keyed by `Quiz::id`.
+///
+/// Like [`Session`](crate::session::Session) it stores no questions and
+/// reads no clock: callers pass the bank and today's [`day`] number.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    #[serde(default)]
+    pub cards: BTreeMap<u32, Card>,
+}
+
+impl Schedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The questions to review on `today`: overdue ones first, most overdue
+    /// at the front, then up to `new` questions never answered before.
+    pub fn due(&self, bank: &[Quiz], today: u64, new: usize) -> Vec<Quiz> {
+        let mut due: Vec<&Quiz> = bank
+            .iter()
+            .filter(|q| self.cards.get(&q.id).is_some_and(|card| card.due <= today))
+            .collect();
+        due.sort_by_key(|q| (self.cards[&q.id].due, q.id));
+        let unseen = bank.iter().filter(|q| !self.cards.contains_key(&q.id)).take(new);
+        due.into_iter().chain(unseen).cloned().collect()
+    }
+
+    /// Updates the question's card with an answer given on `today`.
+    pub fn record(&mut self, id: u32, result: AnswerResult, today: u64) {
+        self.cards.entry(id).or_default().review(result, today);
+    }
+
+    /// The earliest day any known question is due, if there are any.
+    pub fn next_due(&self) -> Option<u64> {
+        self.cards.values().map(|card| card.due).min()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a schedule is always valid JSON")
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(text)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::quiz_bank;
+
+    #[test]
+    fn test_intervals_grow_while_correct() {
+        let mut card = Card::default();
+        let mut intervals = Vec::new();
+        for today in [0, 1, 7] {
+            card.review(AnswerResult::Correct, today);
+            intervals.push(card.interval);
+        }
+
+        assert_eq!(intervals, vec![1, 6, 15]);
+        assert_eq!(card.due, 7 + 15);
+        assert_eq!(card.ease, 2.5);
+    }
+
+    #[test]
+    fn test_wrong_answer_starts_over() {
+        let mut card = Card::default();
+        card.review(AnswerResult::Correct, 0);
+        card.review(AnswerResult::Correct, 1);
+        card.review(AnswerResult::Wrong, 7);
+
+        assert_eq!(card.repetitions, 0);
+        assert_eq!(card.interval, 1);
+        assert_eq!(card.due, 8);
+        assert!(card.ease < 2.5);
+    }
+
+    #[test]
+    fn test_ease_has_a_floor() {
+        let mut card = Card::default();
+        for today in 0..20 {
+            card.review(AnswerResult::Wrong, today);
+        }
+
+        assert_eq!(card.ease, 1.3);
+    }
+
+    #[test]
+    fn test_due_puts_overdue_first_then_new() {
+        let bank = quiz_bank();
+        let mut schedule = Schedule::new();
+        schedule.record(bank[2].id, AnswerResult::Wrong, 10);
+        schedule.record(bank[1].id, AnswerResult::Wrong, 8);
+        schedule.record(bank[0].id, AnswerResult::Correct, 10);
+        schedule.record(bank[0].id, AnswerResult::Correct, 11);
+
+        let ids: Vec<u32> = schedule.due(&bank, 11, 2).iter().map(|q| q.id).collect();
+
+        assert_eq!(ids, vec![bank[1].id, bank[2].id, bank[3].id, bank[4].id]);
+        assert_eq!(schedule.next_due(), Some(9));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut schedule = Schedule::new();
+        schedule.record(4, AnswerResult::Partial, 100);
+
+        let restored = Schedule::from_json(&schedule.to_json()).unwrap();
+
+        assert_eq!(restored, schedule);
+        assert!(Schedule::from_json("{}").unwrap().cards.is_empty());
+    }
+}
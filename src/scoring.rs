@@ -0,0 +1,352 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::engine::{AnswerResult, Attempt, QuizState, Response};
+use crate::quiz::{Level, Quiz};
+
+/// Turns graded attempts into points.
+///
+/// Strategies only look at the answer history, so the same quiz can be
+/// scored several ways after the fact.
+pub trait ScoringStrategy {
+    /// Points for `attempt`, given the attempts that came before it.
+    fn points(&self, quiz: &Quiz, attempt: &Attempt, previous: &[Attempt]) -> f64;
+
+    /// The most points the questions could earn together.
+    fn max_points(&self, quizzes: &[Quiz]) -> f64;
+}
+
+/// One point per fully correct answer, a share of it for partial credit.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Plain;
+
+impl ScoringStrategy for Plain {
+    fn points(&self, _quiz: &Quiz, attempt: &Attempt, _previous: &[Attempt]) -> f64 {
+        attempt.earned
+    }
+
+    fn max_points(&self, quizzes: &[Quiz]) -> f64 {
+        quizzes.len() as f64
+    }
+}
+
+/// Harder questions are worth more: 1 point for Intro up to 4 for
+/// Intermediate.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LevelWeighted;
+
+impl LevelWeighted {
+    pub fn weight(level: Level) -> f64 {
+        match level {
+            Level::Intro => 1.0,
+            Level::Beginner => 2.0,
+            Level::BeginnerPlus => 3.0,
+            Level::Intermediate => 4.0,
+        }
+    }
+}
+
+impl ScoringStrategy for LevelWeighted {
+    fn points(&self, quiz: &Quiz, attempt: &Attempt, _previous: &[Attempt]) -> f64 {
+        attempt.earned * Self::weight(quiz.level)
+    }
+
+    fn max_points(&self, quizzes: &[Quiz]) -> f64 {
+        quizzes.iter().map(|q| Self::weight(q.level)).sum()
+    }
+}
+
+/// Wrong answers cost `penalty` points; questions left unanswered or timed
+/// out cost nothing.
+#[derive(Clone, Copy, Debug)]
+pub struct NegativeMarking {
+    pub penalty: f64,
+}
+
+impl Default for NegativeMarking {
+    fn default() -> Self {
+        Self { penalty: 0.25 }
+    }
+}
+
+impl ScoringStrategy for NegativeMarking {
+    fn points(&self, _quiz: &Quiz, attempt: &Attempt, _previous: &[Attempt]) -> f64 {
+        if attempt.result == AnswerResult::Wrong && !is_unanswered(attempt) {
+            -self.penalty
+        } else {
+            attempt.earned
+        }
+    }
+
+    fn max_points(&self, quizzes: &[Quiz]) -> f64 {
+        quizzes.len() as f64
+    }
+}
+
+/// Up to one extra point per question for answering within `target`,
+/// shrinking linearly to nothing at `target`. Untimed answers get no bonus.
+#[derive(Clone, Copy, Debug)]
+pub struct SpeedBonus {
+    pub target: Duration,
+}
+
+impl Default for SpeedBonus {
+    fn default() -> Self {
+        Self {
+            target: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ScoringStrategy for SpeedBonus {
+    fn points(&self, _quiz: &Quiz, attempt: &Attempt, _previous: &[Attempt]) -> f64 {
+        let target = self.target.as_millis() as f64;
+        let bonus = match attempt.time_taken {
+            Some(ms) if target > 0.0 => (1.0 - ms as f64 / target).max(0.0),
+            _ => 0.0,
+        };
+        attempt.earned * (1.0 + bonus)
+    }
+
+    fn max_points(&self, quizzes: &[Quiz]) -> f64 {
+        2.0 * quizzes.len() as f64
+    }
+}
+
+/// Each fully correct answer in a row raises the multiplier by `step`, up to
+/// `max_multiplier`; anything else resets it.
+#[derive(Clone, Copy, Debug)]
+pub struct Streak {
+    pub step: f64,
+    pub max_multiplier: f64,
+}
+
+impl Default for Streak {
+    fn default() -> Self {
+        Self {
+            step: 0.5,
+            max_multiplier: 3.0,
+        }
+    }
+}
+
+impl Streak {
+    fn multiplier(&self, streak: usize) -> f64 {
+        (1.0 + self.step * streak as f64).min(self.max_multiplier)
+    }
+}
+
+impl ScoringStrategy for Streak {
+    fn points(&self, _quiz: &Quiz, attempt: &Attempt, previous: &[Attempt]) -> f64 {
+        let streak = previous
+            .iter()
+            .rev()
+            .take_while(|a| a.result == AnswerResult::Correct)
+            .count();
+        attempt.earned * self.multiplier(streak)
+    }
+
+    fn max_points(&self, quizzes: &[Quiz]) -> f64 {
+        (0..quizzes.len()).map(|streak| self.multiplier(streak)).sum()
+    }
+}
+
+fn is_unanswered(attempt: &Attempt) -> bool {
+    attempt.timed_out || matches!(&attempt.response, Response::Choices(chosen) if chosen.is_empty())
+}
+
+/// The built-in strategies, by name, for choosing one from a flag or menu.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Scoring {
+    #[default]
+    Plain,
+    LevelWeighted,
+    NegativeMarking,
+    SpeedBonus,
+    Streak,
+}
+
+impl Scoring {
+    pub const ALL: [Scoring; 5] = [
+        Scoring::Plain,
+        Scoring::LevelWeighted,
+        Scoring::NegativeMarking,
+        Scoring::SpeedBonus,
+        Scoring::Streak,
+    ];
+
+    /// The name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Scoring::Plain => "plain",
+            Scoring::LevelWeighted => "level_weighted",
+            Scoring::NegativeMarking => "negative_marking",
+            Scoring::SpeedBonus => "speed_bonus",
+            Scoring::Streak => "streak",
+        }
+    }
+
+    pub fn strategy(self) -> Box<dyn ScoringStrategy> {
+        match self {
+            Scoring::Plain => Box::new(Plain),
+            Scoring::LevelWeighted => Box::new(LevelWeighted),
+            Scoring::NegativeMarking => Box::new(NegativeMarking::default()),
+            Scoring::SpeedBonus => Box::new(SpeedBonus::default()),
+            Scoring::Streak => Box::new(Streak::default()),
+        }
+    }
+}
+
+impl fmt::Display for Scoring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Scoring::Plain => "Plain",
+            Scoring::LevelWeighted => "Level-weighted",
+            Scoring::NegativeMarking => "Negative marking",
+            Scoring::SpeedBonus => "Speed bonus",
+            Scoring::Streak => "Streak multiplier",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl FromStr for Scoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let wanted = s.trim().to_ascii_lowercase().replace('-', "_");
+        Scoring::ALL
+            .into_iter()
+            .find(|scoring| scoring.name() == wanted)
+            .ok_or_else(|| {
+                format!(
+                    "unknown scoring {:?} (expected plain, level_weighted, negative_marking, speed_bonus or streak)",
+                    s
+                )
+            })
+    }
+}
+
+/// Final points for a quiz under one strategy.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScoreReport {
+    pub points: f64,
+    pub max_points: f64,
+}
+
+impl ScoreReport {
+    /// Points as a percentage of the maximum. Negative marking can make
+    /// this negative.
+    pub fn percent(&self) -> f64 {
+        if self.max_points > 0.0 {
+            100.0 * self.points / self.max_points
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Scores `history` against the questions it was played on.
+pub fn score(strategy: &dyn ScoringStrategy, quizzes: &[Quiz], history: &[Attempt]) -> ScoreReport {
+    let points = history
+        .iter()
+        .enumerate()
+        .filter_map(|(i, attempt)| {
+            let quiz = quizzes.iter().find(|q| q.id == attempt.quiz_id)?;
+            Some(strategy.points(quiz, attempt, &history[..i]))
+        })
+        .sum();
+    ScoreReport {
+        points,
+        max_points: strategy.max_points(quizzes),
+    }
+}
+
+impl QuizState {
+    /// Scores the answers so far with `strategy`.
+    pub fn report(&self, quizzes: &[Quiz], strategy: &dyn ScoringStrategy) -> ScoreReport {
+        score(strategy, quizzes, &self.history)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::quiz_bank;
+
+    // Questions 1, 2 and 3 are Intro, Intro and Beginner.
+    fn quizzes() -> Vec<Quiz> {
+        quiz_bank().into_iter().filter(|q| [1, 2, 3].contains(&q.id)).collect()
+    }
+
+    /// Answers the questions in order, right or wrong, with the given times.
+    fn play(quizzes: &[Quiz], answers: &[(bool, Option<u64>)]) -> QuizState {
+        let mut state = QuizState::new();
+        for (quiz, (right, time)) in quizzes.iter().zip(answers) {
+            let correct = quiz.correct.correct_choices()[0];
+            let choice = if *right { correct } else { (correct + 1) % quiz.choices.len() };
+            state.submit(quizzes, Response::Choice(choice));
+            state.history.last_mut().unwrap().time_taken = *time;
+        }
+        state
+    }
+
+    #[test]
+    fn test_plain_counts_correct_answers() {
+        let quizzes = quizzes();
+        let state = play(&quizzes, &[(true, None), (false, None), (true, None)]);
+        let report = state.report(&quizzes, &Plain);
+
+        assert_eq!(report.points, 2.0);
+        assert_eq!(report.max_points, 3.0);
+        assert!((report.percent() - 200.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_level_weighted() {
+        let quizzes = quizzes();
+        let state = play(&quizzes, &[(false, None), (true, None), (true, None)]);
+        let report = state.report(&quizzes, &LevelWeighted);
+
+        assert_eq!(report.points, 1.0 + 2.0);
+        assert_eq!(report.max_points, 4.0);
+    }
+
+    #[test]
+    fn test_negative_marking_skips_unanswered() {
+        let quizzes = quizzes();
+        let mut state = play(&quizzes, &[(true, None), (false, None)]);
+        state.submit(&quizzes, Response::Choices(Vec::new()));
+
+        assert_eq!(state.report(&quizzes, &NegativeMarking::default()).points, 0.75);
+    }
+
+    #[test]
+    fn test_speed_bonus() {
+        let quizzes = quizzes();
+        let state = play(&quizzes, &[(true, Some(0)), (true, Some(15_000)), (true, None)]);
+
+        assert_eq!(state.report(&quizzes, &SpeedBonus::default()).points, 2.0 + 1.5 + 1.0);
+    }
+
+    #[test]
+    fn test_streak_resets_on_a_miss() {
+        let quizzes = quizzes();
+        let state = play(&quizzes, &[(true, None), (true, None), (false, None)]);
+        let streak = Streak::default();
+
+        assert_eq!(state.report(&quizzes, &streak).points, 1.0 + 1.5);
+        assert_eq!(streak.max_points(&quizzes), 1.0 + 1.5 + 2.0);
+    }
+
+    #[test]
+    fn test_scoring_names() {
+        for scoring in Scoring::ALL {
+            assert_eq!(scoring.name().parse::<Scoring>(), Ok(scoring));
+        }
+        assert_eq!("speed-bonus".parse::<Scoring>(), Ok(Scoring::SpeedBonus));
+        assert!("golf".parse::<Scoring>().is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::ops::RangeInclusive;
+
+use crate::quiz::{Level, Quiz};
+use crate::random::Rng;
+
+/// Picks `count` questions in a random order decided by `seed`. The same
+/// bank and seed always give the same questions in the same order.
+pub fn pick_random(quizzes: &[Quiz], count: usize, seed: u64) -> Vec<Quiz> {
+    Query::new().count(count).seed(seed).run(quizzes)
+}
+
+/// Chooses questions from a bank.
+///
+/// Filters combine: a question must carry one of the included tags (if any
+/// are given), none of the excluded ones, sit inside the level range and be
+/// listed in `ids` (if any are given). With a seed the matches are shuffled,
+/// otherwise they keep bank order; `count` then keeps the first ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Query {
+    pub include_tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
+    pub min_level: Option<Level>,
+    pub max_level: Option<Level>,
+    pub ids: Vec<u32>,
+    pub count: Option<usize>,
+    pub seed: Option<u64>,
+}
+
+impl Query {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.include_tags.push(tag.into());
+        self
+    }
+
+    pub fn exclude_tag(mut self, tag: impl Into<String>) -> Self {
+        self.exclude_tags.push(tag.into());
+        self
+    }
+
+    pub fn level(self, level: Level) -> Self {
+        self.levels(level..=level)
+    }
+
+    pub fn levels(mut self, levels: RangeInclusive<Level>) -> Self {
+        self.min_level = Some(*levels.start());
+        self.max_level = Some(*levels.end());
+        self
+    }
+
+    pub fn ids(mut self, ids: impl IntoIterator<Item = u32>) -> Self {
+        self.ids.extend(ids);
+        self
+    }
+
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn matches(&self, quiz: &Quiz) -> bool {
+        let has_tag = |wanted: &String| quiz.tags.iter().any(|tag| tag.eq_ignore_ascii_case(wanted));
+
+        (self.include_tags.is_empty() || self.include_tags.iter().any(has_tag))
+            && !self.exclude_tags.iter().any(has_tag)
+            && self.min_level.is_none_or(|min| quiz.level >= min)
+            && self.max_level.is_none_or(|max| quiz.level <= max)
+            && (self.ids.is_empty() || self.ids.contains(&quiz.id))
+    }
+
+    pub fn run(&self, quizzes: &[Quiz]) -> Vec<Quiz> {
+        let mut picked: Vec<Quiz> = quizzes.iter().filter(|q| self.matches(q)).cloned().collect();
+        if let Some(seed) = self.seed {
+            Rng::new(seed).shuffle(&mut picked);
+        }
+        if let Some(count) = self.count {
+            picked.truncate(count);
+        }
+        picked
+    }
+}
+
+/// Every tag used in the bank, sorted and without duplicates.
+pub fn all_tags(quizzes: &[Quiz]) -> Vec<String> {
+    let mut tags: Vec<String> = quizzes
+        .iter()
+        .flat_map(|q| q.tags.iter().map(|t| t.to_string()))
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::quiz_bank;
+
+    fn ids(quizzes: &[Quiz]) -> Vec<u32> {
+        quizzes.iter().map(|q| q.id).collect()
+    }
+
+    #[test]
+    fn test_same_seed_same_selection() {
+        let bank = quiz_bank();
+
+        assert_eq!(ids(&pick_random(&bank, 5, 1234)), ids(&pick_random(&bank, 5, 1234)));
+    }
+
+    #[test]
+    fn test_different_seeds_differ() {
+        let bank = quiz_bank();
+
+        assert_ne!(ids(&pick_random(&bank, 5, 1)), ids(&pick_random(&bank, 5, 2)));
+    }
+
+    #[test]
+    fn test_count_is_capped_by_bank_size() {
+        let bank = quiz_bank();
+        let picked = pick_random(&bank, bank.len() + 10, 9);
+
+        assert_eq!(picked.len(), bank.len());
+    }
+
+    #[test]
+    fn test_query_without_filters_keeps_bank_order() {
+        let bank = quiz_bank();
+
+        assert_eq!(ids(&Query::new().run(&bank)), ids(&bank));
+    }
+
+    #[test]
+    fn test_query_by_tag() {
+        let bank = quiz_bank();
+        let picked = Query::new().tag("ownership").run(&bank);
+
+        assert!(!picked.is_empty());
+        assert!(picked.iter().all(|q| q.tags.iter().any(|t| t == "ownership")));
+    }
+
+    #[test]
+    fn test_query_excludes_tags() {
+        let bank = quiz_bank();
+        let picked = Query::new().tag("ownership").exclude_tag("function").run(&bank);
+
+        assert!(!ids(&picked).contains(&14));
+        assert!(ids(&picked).contains(&10));
+    }
+
+    #[test]
+    fn test_query_by_level_range() {
+        let bank = quiz_bank();
+        let picked = Query::new().levels(Level::Beginner..=Level::BeginnerPlus).run(&bank);
+
+        assert!(!picked.is_empty());
+        assert!(picked.iter().all(|q| q.level == Level::Beginner || q.level == Level::BeginnerPlus));
+    }
+
+    #[test]
+    fn test_query_by_ids_with_count_and_seed() {
+        let bank = quiz_bank();
+        let query = Query::new().ids([1, 2, 3, 4]).count(2).seed(5);
+        let picked = query.run(&bank);
+
+        assert_eq!(picked.len(), 2);
+        assert!(picked.iter().all(|q| q.id <= 4));
+        assert_eq!(ids(&picked), ids(&query.run(&bank)));
+    }
+
+    #[test]
+    fn test_all_tags_sorted_unique() {
+        let tags = all_tags(&quiz_bank());
+
+        assert!(tags.windows(2).all(|w| w[0] < w[1]));
+        assert!(tags.contains(&"ownership".to_string()));
+    }
+}
@@ -0,0 +1,232 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{Credit, QuizState, Response};
+use crate::quiz::Quiz;
+
+/// A quiz in progress, saved so it can be picked up again later.
+///
+/// Only question ids and responses are stored; resuming looks the questions
+/// up in the bank and replays the answers through a fresh [`QuizState`].
+/// Times are milliseconds since the Unix epoch, passed in by the caller so
+/// the library never reads a clock.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    /// The questions being played, in play order.
+    pub quiz_ids: Vec<u32>,
+    /// Seed the questions were picked with, if they were picked at random.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub choice_seed: Option<u64>,
+    #[serde(default)]
+    pub credit: Credit,
+    /// A round retrying the questions missed before, which is practice and
+    /// is not logged to the history.
+    #[serde(default)]
+    pub retry: bool,
+    /// Submitted answers, one per question from the start.
+    #[serde(default)]
+    pub answers: Vec<AnsweredQuestion>,
+    pub started_at: u64,
+    pub updated_at: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnsweredQuestion {
+    pub id: u32,
+    pub response: Response,
+    pub answered_at: u64,
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    Json(serde_json::Error),
+    /// The question is in neither the built-in bank nor the loaded packs.
+    UnknownQuestion(u32),
+    /// An answer is stored for a different question than the one at its
+    /// position.
+    Mismatch { expected: u32, found: u32 },
+    TooManyAnswers,
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Json(err) => write!(f, "invalid saved session: {}", err),
+            SessionError::UnknownQuestion(id) => write!(
+                f,
+                "saved session uses question {} which is not loaded (pass the same packs again)",
+                id
+            ),
+            SessionError::Mismatch { expected, found } => write!(
+                f,
+                "saved session answers question {} where question {} was expected",
+                found, expected
+            ),
+            SessionError::TooManyAnswers => write!(f, "saved session has more answers than questions"),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SessionError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Session {
+    /// Starts recording a quiz over `quizzes`, taking the scoring and choice
+    /// order settings from `state`.
+    pub fn new(quizzes: &[Quiz], state: &QuizState, seed: Option<u64>, now: u64) -> Self {
+        Self {
+            quiz_ids: quizzes.iter().map(|q| q.id).collect(),
+            seed,
+            choice_seed: state.choice_seed,
+            credit: state.credit,
+            retry: false,
+            answers: Vec::new(),
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn as_retry(mut self) -> Self {
+        self.retry = true;
+        self
+    }
+
+    pub fn record(&mut self, id: u32, response: Response, now: u64) {
+        self.answers.push(AnsweredQuestion {
+            id,
+            response,
+            answered_at: now,
+        });
+        self.updated_at = now;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.answers.len() >= self.quiz_ids.len()
+    }
+
+    /// Rebuilds the question list and replays every answer, leaving the
+    /// state on the first unanswered question.
+    pub fn resume(&self, bank: &[Quiz]) -> Result<(Vec<Quiz>, QuizState), SessionError> {
+        let quizzes = self
+            .quiz_ids
+            .iter()
+            .map(|id| {
+                bank.iter()
+                    .find(|q| q.id == *id)
+                    .cloned()
+                    .ok_or(SessionError::UnknownQuestion(*id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if self.answers.len() > quizzes.len() {
+            return Err(SessionError::TooManyAnswers);
+        }
+
+        let mut state = QuizState::new().with_credit(self.credit);
+        state.choice_seed = self.choice_seed;
+        for (quiz, answered) in quizzes.iter().zip(&self.answers) {
+            if quiz.id != answered.id {
+                return Err(SessionError::Mismatch {
+                    expected: quiz.id,
+                    found: answered.id,
+                });
+            }
+            state.submit(&quizzes, answered.response.clone());
+        }
+
+        Ok((quizzes, state))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a session is always valid JSON")
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, SessionError> {
+        serde_json::from_str(text).map_err(SessionError::Json)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Phase;
+    use crate::quiz::quiz_bank;
+
+    fn started(ids: &[u32]) -> (Vec<Quiz>, Session) {
+        let bank = quiz_bank();
+        let quizzes: Vec<Quiz> = ids.iter().map(|id| bank.iter().find(|q| q.id == *id).unwrap().clone()).collect();
+        let session = Session::new(&quizzes, &QuizState::new().with_shuffled_choices(7), Some(42), 1_000);
+        (quizzes, session)
+    }
+
+    #[test]
+    fn test_resume_replays_answers() {
+        let (quizzes, mut session) = started(&[3, 1, 2]);
+        let correct = quizzes[0].correct.correct_choices()[0];
+        session.record(3, Response::Choice(correct), 2_000);
+        session.record(1, Response::Choice(usize::MAX), 3_000);
+
+        let (resumed, state) = session.resume(&quiz_bank()).unwrap();
+
+        assert_eq!(resumed.iter().map(|q| q.id).collect::<Vec<_>>(), vec![3, 1, 2]);
+        assert_eq!(state.current, 2);
+        assert_eq!(state.score, 1);
+        assert_eq!(state.choice_seed, Some(7));
+        assert_eq!(state.phase(), Phase::Answering);
+        assert_eq!(session.updated_at, 3_000);
+        assert!(!session.is_finished());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let (_, mut session) = started(&[22, 24]);
+        session.record(22, Response::Text("12".to_string()), 1_500);
+        session.record(24, Response::Blanks(vec!["mut".to_string()]), 1_600);
+
+        let restored = Session::from_json(&session.to_json()).unwrap();
+
+        assert_eq!(restored, session);
+        assert!(restored.is_finished());
+        assert_eq!(restored.resume(&quiz_bank()).unwrap().1.score, 2);
+    }
+
+    #[test]
+    fn test_unknown_question_is_an_error() {
+        let (_, mut session) = started(&[1]);
+        session.quiz_ids.push(9_999);
+
+        assert!(matches!(session.resume(&quiz_bank()), Err(SessionError::UnknownQuestion(9_999))));
+    }
+
+    #[test]
+    fn test_answer_for_wrong_question_is_an_error() {
+        let (_, mut session) = started(&[1, 2]);
+        session.record(2, Response::Choice(0), 2_000);
+
+        assert!(matches!(
+            session.resume(&quiz_bank()),
+            Err(SessionError::Mismatch { expected: 1, found: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_retry_flag_is_saved() {
+        let (_, session) = started(&[1]);
+        let retry = session.clone().as_retry();
+
+        assert!(Session::from_json(&retry.to_json()).unwrap().retry);
+        // Sessions saved before the flag existed are ordinary quizzes.
+        let old = session.to_json().replace("\"retry\": false,", "");
+        assert!(!Session::from_json(&old).unwrap().retry);
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{AnswerResult, Attempt};
+use crate::quiz::{Level, Quiz};
+
+/// One graded answer, kept across quizzes for statistics.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub quiz_id: u32,
+    pub result: AnswerResult,
+    /// Credit earned, from 0.0 to 1.0.
+    pub earned: f64,
+    /// Milliseconds since the Unix epoch.
+    pub answered_at: u64,
+}
+
+/// Every answer given in finished quizzes, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default)]
+    pub records: Vec<Record>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the answers of a finished quiz.
+    pub fn add(&mut self, attempts: &[Attempt], now: u64) {
+        self.records.extend(attempts.iter().map(|attempt| Record {
+            quiz_id: attempt.quiz_id,
+            result: attempt.result,
+            earned: attempt.earned,
+            answered_at: now,
+        }));
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a history is always valid JSON")
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(text)
+    }
+}
+
+/// Answers counted for one tag, level or the whole history.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Tally {
+    pub answered: usize,
+    /// Fully correct answers.
+    pub correct: usize,
+    /// Credit earned, partial answers included.
+    pub earned: f64,
+}
+
+impl Tally {
+    fn add(&mut self, record: &Record) {
+        self.answered += 1;
+        self.earned += record.earned;
+        if record.result == AnswerResult::Correct {
+            self.correct += 1;
+        }
+    }
+
+    /// Credit earned as a percentage of the answers.
+    pub fn accuracy(&self) -> f64 {
+        if self.answered == 0 {
+            0.0
+        } else {
+            100.0 * self.earned / self.answered as f64
+        }
+    }
+}
+
+/// Accuracy per tag and per level.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub overall: Tally,
+    pub by_tag: BTreeMap<String, Tally>,
+    pub by_level: BTreeMap<Level, Tally>,
+}
+
+impl Stats {
+    /// Counts `records` against the questions in `bank`. Answers to
+    /// questions no longer in the bank are left out, since their tags and
+    /// level are unknown.
+    pub fn from_records(bank: &[Quiz], records: &[Record]) -> Self {
+        let mut stats = Self::default();
+        for record in records {
+            let Some(quiz) = bank.iter().find(|q| q.id == record.quiz_id) else {
+                continue;
+            };
+            stats.overall.add(record);
+            stats.by_level.entry(quiz.level).or_default().add(record);
+            for tag in &quiz.tags {
+                stats.by_tag.entry(tag.to_lowercase()).or_default().add(record);
+            }
+        }
+        stats
+    }
+
+    /// Tags from least to most accurate, the ones answered most first on a
+    /// tie: the topics most worth studying come first.
+    pub fn weakest_tags(&self) -> Vec<(&str, Tally)> {
+        let mut tags: Vec<(&str, Tally)> = self.by_tag.iter().map(|(tag, tally)| (tag.as_str(), *tally)).collect();
+        tags.sort_by(|a, b| {
+            a.1.accuracy()
+                .total_cmp(&b.1.accuracy())
+                .then(b.1.answered.cmp(&a.1.answered))
+                .then(a.0.cmp(b.0))
+        });
+        tags
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{QuizState, Response};
+    use crate::quiz::quiz_bank;
+
+    fn record(quiz_id: u32, earned: f64) -> Record {
+        let result = if earned == 1.0 { AnswerResult::Correct } else { AnswerResult::Wrong };
+        Record {
+            quiz_id,
+            result,
+            earned,
+            answered_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_counts_per_tag_and_level() {
+        let bank = quiz_bank();
+        let quiz = &bank[0];
+        let stats = Stats::from_records(&bank, &[record(quiz.id, 1.0), record(quiz.id, 0.0), record(9_999, 1.0)]);
+
+        assert_eq!(stats.overall.answered, 2);
+        assert_eq!(stats.overall.correct, 1);
+        assert_eq!(stats.by_level[&quiz.level].accuracy(), 50.0);
+        for tag in &quiz.tags {
+            assert_eq!(stats.by_tag[&tag.to_lowercase()].answered, 2);
+        }
+    }
+
+    #[test]
+    fn test_weakest_tags_first() {
+        // Question 1 is tagged basics and files, question 2 basics and
+        // mutability.
+        let stats = Stats::from_records(&quiz_bank(), &[record(1, 1.0), record(1, 1.0), record(2, 0.0)]);
+
+        let order: Vec<&str> = stats.weakest_tags().into_iter().map(|(tag, _)| tag).collect();
+        assert_eq!(order, vec!["mutability", "basics", "files"]);
+    }
+
+    #[test]
+    fn test_history_from_a_quiz() {
+        let bank = quiz_bank();
+        let quizzes = &bank[..2];
+        let mut state = QuizState::new();
+        state.submit(quizzes, Response::Choice(quizzes[0].correct.correct_choices()[0]));
+        state.submit(quizzes, Response::Choice(usize::MAX));
+
+        let mut history = History::new();
+        history.add(&state.history, 5_000);
+        let restored = History::from_json(&history.to_json()).unwrap();
+
+        assert_eq!(restored, history);
+        assert_eq!(restored.records[0].result, AnswerResult::Correct);
+        assert_eq!(restored.records[1].answered_at, 5_000);
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use regex::Regex;
+
+use crate::quiz::{Answer, Matching, Quiz, TextAnswer, BLANK};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found in one question of a bank.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Position of the question in the validated slice.
+    pub index: usize,
+    pub id: u32,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[quiz {}]: {}", self.severity, self.id, self.message)
+    }
+}
+
+/// Checks a question bank for entries that would be broken or confusing to
+/// play, returning the problems in bank order.
+pub fn validate(quizzes: &[Quiz]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    for (index, quiz) in quizzes.iter().enumerate() {
+        let mut report = |severity, message: String| {
+            diagnostics.push(Diagnostic {
+                severity,
+                index,
+                id: quiz.id,
+                message,
+            });
+        };
+
+        if !seen_ids.insert(quiz.id) {
+            report(Severity::Error, "duplicate id, already used by an earlier question".to_string());
+        }
+
+        if quiz.title.trim().is_empty() {
+            report(Severity::Error, "title is empty".to_string());
+        }
+        if quiz.question.trim().is_empty() {
+            report(Severity::Error, "question is empty".to_string());
+        }
+        if quiz.explanation.trim().is_empty() {
+            report(Severity::Error, "explanation is empty".to_string());
+        }
+
+        match &quiz.correct {
+            Answer::Single(_) | Answer::Multiple(_) => {
+                if quiz.choices.len() < 2 {
+                    report(
+                        Severity::Error,
+                        format!("needs at least 2 choices, found {}", quiz.choices.len()),
+                    );
+                }
+                let correct = quiz.correct.correct_choices();
+                if correct.is_empty() {
+                    report(Severity::Error, "has no correct answer".to_string());
+                }
+                for (i, index) in correct.iter().enumerate() {
+                    if *index >= quiz.choices.len() {
+                        report(
+                            Severity::Error,
+                            format!(
+                                "correct answer index {} is out of range for {} choices",
+                                index,
+                                quiz.choices.len()
+                            ),
+                        );
+                    } else if correct[..i].contains(index) {
+                        report(Severity::Error, format!("correct answer index {} is listed twice", index));
+                    }
+                }
+            }
+            Answer::Text(text) => {
+                check_text_answer(text, "", &mut report);
+                if !quiz.choices.is_empty() {
+                    report(Severity::Warning, "choices are ignored for typed answers".to_string());
+                }
+            }
+            Answer::Order { order } => {
+                if quiz.choices.len() < 2 {
+                    report(
+                        Severity::Error,
+                        format!("needs at least 2 lines to order, found {}", quiz.choices.len()),
+                    );
+                }
+                let mut sorted = order.clone();
+                sorted.sort_unstable();
+                if !sorted.iter().copied().eq(0..quiz.choices.len()) {
+                    report(
+                        Severity::Error,
+                        format!("order must list every one of the {} lines exactly once", quiz.choices.len()),
+                    );
+                }
+            }
+            Answer::Blanks { blanks } => {
+                if quiz.code.is_none() {
+                    report(Severity::Error, "has blanks to fill but no code".to_string());
+                } else if quiz.blank_count() != blanks.len() {
+                    report(
+                        Severity::Error,
+                        format!(
+                            "code has {} {} placeholder(s) but {} blank answer(s)",
+                            quiz.blank_count(),
+                            BLANK,
+                            blanks.len()
+                        ),
+                    );
+                }
+                for (i, blank) in blanks.iter().enumerate() {
+                    check_text_answer(blank, &format!("blank {}: ", i + 1), &mut report);
+                }
+                if !quiz.choices.is_empty() {
+                    report(Severity::Warning, "choices are ignored for fill-in-the-blank answers".to_string());
+                }
+            }
+        }
+
+        // Lines of an ordering question may legitimately repeat, e.g. `}`.
+        let ordering = matches!(quiz.correct, Answer::Order { .. });
+        let mut seen_choices = HashSet::new();
+        for (i, choice) in quiz.choices.iter().enumerate() {
+            if choice.trim().is_empty() {
+                report(Severity::Error, format!("choice {} is empty", i + 1));
+            } else if !seen_choices.insert(choice.trim()) && !ordering {
+                report(
+                    Severity::Error,
+                    format!("choice {} duplicates an earlier choice: {:?}", i + 1, choice),
+                );
+            }
+        }
+
+        if quiz.expect.is_some() && quiz.code.is_none() {
+            report(Severity::Error, "has an expected outcome but no code".to_string());
+        }
+
+        if quiz.tags.is_empty() {
+            report(Severity::Warning, "has no tags".to_string());
+        }
+    }
+
+    diagnostics
+}
+
+fn check_text_answer(text: &TextAnswer, prefix: &str, report: &mut impl FnMut(Severity, String)) {
+    if text.accepted.is_empty() {
+        report(Severity::Error, format!("{}has no accepted answer", prefix));
+    }
+    for accepted in &text.accepted {
+        if accepted.trim().is_empty() {
+            report(Severity::Error, format!("{}accepted answer is empty", prefix));
+        } else if text.matching == Matching::Regex
+            && let Err(err) = Regex::new(accepted)
+        {
+            report(
+                Severity::Error,
+                format!("{}invalid answer pattern {:?}: {}", prefix, accepted, err),
+            );
+        }
+    }
+}
+
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(Diagnostic::is_error)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::{quiz_bank, Level};
+
+    fn mock_quiz(id: u32) -> Quiz {
+        Quiz {
+            id,
+            title: "Test".into(),
+            question: "Q?".into(),
+            code: None,
+            choices: vec!["A".into(), "B".into(), "C".into()],
+            correct: Answer::Single(0),
+            explanation: "Because.".into(),
+            tags: vec!["test".into()],
+            level: Level::Intro,
+            expect: None,
+        }
+    }
+
+    fn messages(quizzes: &[Quiz]) -> Vec<(Severity, u32)> {
+        validate(quizzes)
+            .into_iter()
+            .map(|d| (d.severity, d.id))
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_bank_is_valid() {
+        let diagnostics = validate(&quiz_bank());
+
+        assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+    }
+
+    #[test]
+    fn test_correct_out_of_range_is_error() {
+        let mut quiz = mock_quiz(1);
+        quiz.correct = Answer::Single(3);
+
+        assert_eq!(messages(&[quiz]), vec![(Severity::Error, 1)]);
+    }
+
+    #[test]
+    fn test_multiple_answer_indices_checked() {
+        let mut quiz = mock_quiz(1);
+        quiz.correct = Answer::Multiple(vec![0, 5, 0]);
+
+        assert_eq!(validate(&[quiz]).len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_answer_pattern_is_error() {
+        let mut quiz = mock_quiz(1);
+        quiz.choices.clear();
+        quiz.correct = Answer::Text(TextAnswer {
+            accepted: vec!["error[".into()],
+            matching: Matching::Regex,
+        });
+
+        assert_eq!(messages(&[quiz]), vec![(Severity::Error, 1)]);
+    }
+
+    #[test]
+    fn test_order_must_be_a_permutation() {
+        let mut quiz = mock_quiz(1);
+        quiz.correct = Answer::Order { order: vec![0, 1, 1] };
+
+        assert_eq!(messages(&[quiz]), vec![(Severity::Error, 1)]);
+    }
+
+    #[test]
+    fn test_blank_count_must_match_code() {
+        let mut quiz = mock_quiz(1);
+        quiz.choices.clear();
+        quiz.code = Some("let ____ x = 1; let y: ____ = 2;".into());
+        quiz.correct = Answer::Blanks {
+            blanks: vec![TextAnswer {
+                accepted: vec!["mut".into()],
+                matching: Matching::Exact,
+            }],
+        };
+
+        assert_eq!(messages(&[quiz]), vec![(Severity::Error, 1)]);
+    }
+
+    #[test]
+    fn test_duplicate_id_reported_on_later_question() {
+        let diagnostics = validate(&[mock_quiz(7), mock_quiz(8), mock_quiz(7)]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].index, 2);
+        assert!(diagnostics[0].is_error());
+    }
+
+    #[test]
+    fn test_duplicate_choice_is_error() {
+        let mut quiz = mock_quiz(1);
+        quiz.choices[2] = " A".into();
+
+        assert_eq!(messages(&[quiz]), vec![(Severity::Error, 1)]);
+    }
+
+    #[test]
+    fn test_empty_explanation_is_error() {
+        let mut quiz = mock_quiz(1);
+        quiz.explanation = " ".into();
+        let diagnostics = validate(&[quiz]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(has_errors(&diagnostics));
+    }
+}
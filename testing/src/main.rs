@@ -1,3 +1,231 @@
+//! Compiles every `Quiz::code` snippet with the local `rustc` and checks the
+//! result against the question's `expect` metadata.
+//!
+//! Usage: `cargo run -- [PACK...]` checks the built-in bank plus any packs.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+use rust_quiz::loader::load_bank;
+use rust_quiz::quiz::{Answer, Expect, Quiz, BLANK};
+
+/// What happened to a snippet.
+#[derive(Debug)]
+enum Outcome {
+    CompileError { stderr: String },
+    Ran { success: bool, stdout: String, stderr: String },
+}
+
+/// Snippets are mostly statements, so they get a `fn main` around them
+/// unless they already have one.
+fn wrap(code: &str) -> String {
+    if code.contains("fn main") {
+        code.to_string()
+    } else {
+        format!("fn main() {{\n{}\n}}\n", code)
+    }
+}
+
+/// Fill-in-the-blank snippets are checked with each blank replaced by its
+/// first accepted answer.
+fn fill_blanks(quiz: &Quiz, code: &str) -> String {
+    let Answer::Blanks { blanks } = &quiz.correct else {
+        return code.to_string();
+    };
+    let mut filled = String::new();
+    let mut parts = code.split(BLANK);
+    filled.push_str(parts.next().unwrap_or_default());
+    for (i, part) in parts.enumerate() {
+        let answer = blanks.get(i).and_then(|b| b.accepted.first());
+        filled.push_str(answer.map_or(BLANK, |a| a.as_ref()));
+        filled.push_str(part);
+    }
+    filled
+}
+
+fn rustc() -> String {
+    env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string())
+}
+
+fn work_dir() -> io::Result<PathBuf> {
+    let dir = env::temp_dir().join(format!("rust_quiz_snippets_{}", process::id()));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn run_snippet(quiz_id: u32, code: &str, dir: &Path) -> io::Result<Outcome> {
+    let source = dir.join(format!("quiz_{}.rs", quiz_id));
+    let binary = dir.join(format!("quiz_{}{}", quiz_id, env::consts::EXE_SUFFIX));
+    fs::write(&source, wrap(code))?;
+
+    // Only warn-level lints are silenced: deny-by-default lints such as
+    // `overflowing_literals` are part of what the questions ask about.
+    let compiled = Command::new(rustc())
+        .args(["--edition", "2024", "-A", "warnings", "-o"])
+        .arg(&binary)
+        .arg(&source)
+        .output()?;
+    if !compiled.status.success() {
+        return Ok(Outcome::CompileError {
+            stderr: String::from_utf8_lossy(&compiled.stderr).into_owned(),
+        });
+    }
+
+    let ran = Command::new(&binary).output()?;
+    Ok(Outcome::Ran {
+        success: ran.status.success(),
+        stdout: String::from_utf8_lossy(&ran.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&ran.stderr).into_owned(),
+    })
+}
+
+fn check(expect: &Expect, outcome: &Outcome) -> Result<(), String> {
+    match (expect, outcome) {
+        (Expect::CompileError(text), Outcome::CompileError { stderr }) => {
+            if stderr.contains(text.as_ref()) {
+                Ok(())
+            } else {
+                Err(format!("expected a compile error containing {:?}, got:\n{}", text, stderr))
+            }
+        }
+        (_, Outcome::CompileError { stderr }) => {
+            Err(format!("expected the snippet to compile, got:\n{}", stderr))
+        }
+        (Expect::CompileError(text), Outcome::Ran { .. }) => {
+            Err(format!("expected a compile error containing {:?}, but it compiled", text))
+        }
+        (Expect::Panics, Outcome::Ran { success, stderr, .. }) => {
+            if !success && stderr.contains("panicked") {
+                Ok(())
+            } else {
+                Err("expected a panic at runtime".to_string())
+            }
+        }
+        (_, Outcome::Ran { success: false, stderr, .. }) => {
+            Err(format!("expected a successful run, got:\n{}", stderr))
+        }
+        (Expect::Runs, Outcome::Ran { .. }) => Ok(()),
+        (Expect::Stdout(expected), Outcome::Ran { stdout, .. }) => {
+            if stdout.trim() == expected.trim() {
+                Ok(())
+            } else {
+                Err(format!("expected stdout {:?}, got {:?}", expected, stdout.trim()))
+            }
+        }
+        (Expect::Stderr(expected), Outcome::Ran { stderr, .. }) => {
+            match expected.iter().find(|text| !stderr.lines().any(|line| line.contains(text.as_ref()))) {
+                None => Ok(()),
+                Some(missing) => Err(format!("expected a stderr line containing {:?}, got:\n{}", missing, stderr)),
+            }
+        }
+    }
+}
+
+/// Checks every question that has both code and an expectation, returning
+/// `(id, message)` for each mismatch.
+fn check_all(quizzes: &[Quiz]) -> io::Result<Vec<(u32, String)>> {
+    let dir = work_dir()?;
+    let mut failures = Vec::new();
+
+    for quiz in quizzes {
+        let (Some(code), Some(expect)) = (&quiz.code, &quiz.expect) else {
+            continue;
+        };
+        let outcome = run_snippet(quiz.id, &fill_blanks(quiz, code), &dir)?;
+        match check(expect, &outcome) {
+            Ok(()) => println!("ok      [{}] {}", quiz.id, quiz.title),
+            Err(message) => {
+                println!("FAILED  [{}] {}", quiz.id, quiz.title);
+                failures.push((quiz.id, message));
+            }
+        }
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+    Ok(failures)
+}
+
 fn main() {
-    println!("Hello, world!");
+    let pack_paths: Vec<String> = env::args().skip(1).collect();
+    let quizzes = match load_bank(&pack_paths) {
+        Ok(quizzes) => quizzes,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
+
+    let failures = match check_all(&quizzes) {
+        Ok(failures) => failures,
+        Err(err) => {
+            eprintln!("cannot run {}: {}", rustc(), err);
+            process::exit(2);
+        }
+    };
+
+    for (id, message) in &failures {
+        println!("\n---- quiz {} ----\n{}", id, message);
+    }
+
+    if failures.is_empty() {
+        println!("\nall snippets behave as their questions claim");
+    } else {
+        println!("\n{} snippet(s) do not match their questions", failures.len());
+        process::exit(1);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_quiz::quiz::quiz_bank;
+
+    #[test]
+    fn test_wrap_adds_main() {
+        assert_eq!(wrap("let x = 1;"), "fn main() {\nlet x = 1;\n}\n");
+        assert_eq!(wrap("fn main() {}"), "fn main() {}");
+    }
+
+    #[test]
+    fn test_fill_blanks_uses_first_accepted_answer() {
+        let quiz = quiz_bank().into_iter().find(|q| q.id == 24).unwrap();
+        let code = quiz.code.as_deref().unwrap();
+
+        assert!(fill_blanks(&quiz, code).starts_with("let mut count = 0;"));
+    }
+
+    #[test]
+    fn test_expected_stdout_ignores_trailing_newline() {
+        let outcome = Outcome::Ran {
+            success: true,
+            stdout: "12\n".to_string(),
+            stderr: String::new(),
+        };
+
+        assert!(check(&Expect::Stdout("12".into()), &outcome).is_ok());
+        assert!(check(&Expect::CompileError("E0382".into()), &outcome).is_err());
+    }
+
+    #[test]
+    fn test_expected_stderr_needs_every_line() {
+        let outcome = Outcome::Ran {
+            success: true,
+            stdout: String::new(),
+            stderr: "[src/main.rs:3:1] x = 42\n".to_string(),
+        };
+
+        assert!(check(&Expect::Stderr(vec!["x = 42".into()]), &outcome).is_ok());
+        assert!(check(&Expect::Stderr(vec!["x = 42".into(), "y = 42".into()]), &outcome).is_err());
+    }
+
+    #[test]
+    fn test_builtin_snippets_match_expectations() {
+        let failures = check_all(&quiz_bank()).unwrap();
+
+        assert!(failures.is_empty(), "{:#?}", failures);
+    }
 }
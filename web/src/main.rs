@@ -1,8 +1,24 @@
 use leptos::prelude::*;
-use rust_quiz::quiz::quiz_bank;
-use rust_quiz::engine::QuizState;
-use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rust_quiz::quiz::{quiz_bank, Answer, Level, Quiz, BLANK};
+use std::time::Duration;
+
+use rust_quiz::engine::{
+    describe_response, describe_solution, AnswerResult, Attempt, Credit, Phase, QuizState, Response, Timeout,
+};
+use rust_quiz::select::{all_tags, Query};
+use rust_quiz::session::Session;
+use rust_quiz::scoring::Scoring;
+use rust_quiz::schedule::{day, Schedule, NEW_PER_DAY};
+use rust_quiz::adaptive::Adaptive;
+use rust_quiz::stats::{History, Stats, Tally};
+use rust_quiz::highlight::{self, Kind};
+
+/// localStorage key holding the unfinished quiz, if any.
+const SESSION_KEY: &str = "rust_quiz.session";
+/// localStorage key holding the spaced-repetition schedule.
+const SCHEDULE_KEY: &str = "rust_quiz.schedule";
+/// localStorage key holding the answers of finished quizzes.
+const HISTORY_KEY: &str = "rust_quiz.history";
 
 fn main() {
     mount_to_body(|| view! { <App/> })
@@ -13,19 +29,284 @@ enum GameMode {
     NotSelected,
     Random5,
     AllQuestions,
+    DailyReview,
+    Adaptive,
+    Statistics,
+    /// Questions missed in the previous round.
+    Retry,
+}
+
+/// `code` with Rust syntax highlighting and line numbers. `blank(i)` draws
+/// the `i`th fill-in-the-blank placeholder.
+fn code_view(code: &str, blank: impl Fn(usize) -> AnyView) -> AnyView {
+    let mut blanks = 0;
+    let lines = highlight::lines(code)
+        .into_iter()
+        .enumerate()
+        .map(|(n, tokens)| {
+            let tokens = tokens
+                .into_iter()
+                .map(|token| match token.kind {
+                    Kind::Blank => {
+                        blanks += 1;
+                        blank(blanks - 1)
+                    }
+                    Kind::Plain => token.text.to_string().into_any(),
+                    kind => view! { <span class=format!("tok-{}", kind.name())>{token.text.to_string()}</span> }.into_any(),
+                })
+                .collect_view();
+            view! {
+                <span class="line-number">{n + 1}</span>
+                {tokens}
+                "\n"
+            }
+        })
+        .collect_view();
+    view! { <pre class="mb-3 code-block"><code>{lines}</code></pre> }.into_any()
+}
+
+fn now_millis() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
+fn load_saved_session() -> Option<Session> {
+    let text = storage()?.get_item(SESSION_KEY).ok()??;
+    Session::from_json(&text).ok().filter(|session| !session.is_finished())
+}
+
+/// The mode a saved session is picked back up in.
+fn resumed_mode(saved: &Session) -> GameMode {
+    if saved.retry {
+        GameMode::Retry
+    } else if saved.seed.is_some() {
+        GameMode::Random5
+    } else {
+        GameMode::AllQuestions
+    }
+}
+
+/// Whether a finished game is logged to the history. Retry rounds are
+/// practice and are left out, so the statistics count each question once
+/// per quiz.
+fn is_logged(mode: GameMode) -> bool {
+    mode != GameMode::Retry
+}
+
+fn store_session(session: Option<&Session>) {
+    let Some(storage) = storage() else { return };
+    // Storage can be full or disabled; the quiz still works without it.
+    let _ = match session {
+        Some(session) => storage.set_item(SESSION_KEY, &session.to_json()),
+        None => storage.remove_item(SESSION_KEY),
+    };
+}
+
+fn load_schedule() -> Schedule {
+    storage()
+        .and_then(|storage| storage.get_item(SCHEDULE_KEY).ok().flatten())
+        .and_then(|text| Schedule::from_json(&text).ok())
+        .unwrap_or_default()
+}
+
+fn store_schedule(schedule: &Schedule) {
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(SCHEDULE_KEY, &schedule.to_json());
+    }
+}
+
+fn load_history() -> History {
+    storage()
+        .and_then(|storage| storage.get_item(HISTORY_KEY).ok().flatten())
+        .and_then(|text| History::from_json(&text).ok())
+        .unwrap_or_default()
+}
+
+fn store_history(history: &History) {
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(HISTORY_KEY, &history.to_json());
+    }
+}
+
+/// One row of a statistics table.
+fn tally_row(label: String, tally: Tally) -> impl IntoView {
+    let accuracy = tally.accuracy();
+    let style = if accuracy >= 80.0 { "success" } else if accuracy >= 50.0 { "warning" } else { "danger" };
+    view! {
+        <tr>
+            <td>{label}</td>
+            <td class="text-end">{tally.answered}</td>
+            <td class="text-end">{tally.correct}</td>
+            <td class="w-50">
+                <div class="progress" role="progressbar" aria-valuenow=accuracy.round() aria-valuemin="0" aria-valuemax="100">
+                    <div class=format!("progress-bar bg-{}", style) style=format!("width: {:.0}%", accuracy)>
+                        {format!("{:.0}%", accuracy)}
+                    </div>
+                </div>
+            </td>
+        </tr>
+    }
 }
 
 #[component]
 fn App() -> impl IntoView {
     let all_quizzes = StoredValue::new(quiz_bank());
-    let _state = QuizState::new();
-    
+    let tags = StoredValue::new(all_tags(&all_quizzes.get_value()));
+    // Gameplay lives in the engine; the signals below only configure a game.
+    let state = RwSignal::new(QuizState::new());
+    let current_index = Memo::new(move |_| state.with(|s| s.current));
+    let finished = Memo::new(move |_| state.with(|s| s.finished));
+    let phase = Memo::new(move |_| state.with(QuizState::phase));
+
     let (game_mode, set_game_mode) = signal(GameMode::NotSelected);
-    let (quiz_list, set_quiz_list) = signal(Vec::new());
-    let (current_index, set_current_index) = signal(0usize);
-    let (selected_answer, set_selected_answer) = signal(None::<usize>);
-    let (feedback, set_feedback) = signal(None::<(bool, String)>);
-    let (score, set_score) = signal(0usize);
+    let (quiz_list, set_quiz_list) = signal(Vec::<Quiz>::new());
+    let (seed, set_seed) = signal(None::<u64>);
+    let (seed_input, set_seed_input) = signal(String::new());
+    let (shuffle_choices, set_shuffle_choices) = signal(false);
+    let (partial_credit, set_partial_credit) = signal(false);
+    let (exam_mode, set_exam_mode) = signal(false);
+    // Time limits in seconds, 0 meaning none.
+    let (question_limit, set_question_limit) = signal(0u64);
+    let (total_limit, set_total_limit) = signal(0u64);
+    let (scoring, set_scoring) = signal(Scoring::default());
+    let (now, set_now) = signal(now_millis());
+    let session = RwSignal::new(None::<Session>);
+    let schedule = RwSignal::new(load_schedule());
+    let adaptive = RwSignal::new(None::<Adaptive>);
+    let history = RwSignal::new(load_history());
+    let due_today = move || schedule.with(|s| s.due(&all_quizzes.get_value(), day(now_millis()), NEW_PER_DAY));
+
+    // Pick an unfinished quiz back up after a reload.
+    if let Some(saved) = load_saved_session()
+        && let Ok((quizzes, mut restored)) = saved.resume(&all_quizzes.get_value())
+    {
+        restored.start_question(&now_millis);
+        state.set(restored);
+        set_quiz_list.set(quizzes);
+        set_seed.set(saved.seed);
+        set_game_mode.set(resumed_mode(&saved));
+        session.set(Some(saved));
+    }
+    Effect::new(move |_| session.with(|saved| store_session(saved.as_ref().filter(|s| !s.is_finished()))));
+    Effect::new(move |_| schedule.with(store_schedule));
+    // Log each quiz once, as it finishes.
+    Effect::new(move |_| {
+        if finished.get() && is_logged(game_mode.get_untracked()) {
+            let attempts = state.with_untracked(|s| s.history.clone());
+            history.update(|h| h.add(&attempts, now_millis()));
+            history.with_untracked(store_history);
+        }
+    });
+    let (filter_tag, set_filter_tag) = signal(String::new());
+    let (min_level, set_min_level) = signal(Level::Intro);
+    let (max_level, set_max_level) = signal(Level::Intermediate);
+    // Questions are chosen from the ones matching the menu filters.
+    let filters = move || {
+        let query = Query::new().levels(min_level.get()..=max_level.get());
+        match filter_tag.get() {
+            tag if tag.is_empty() => query,
+            tag => query.tag(tag),
+        }
+    };
+    let matching_count = move || filters().run(&all_quizzes.get_value()).len();
+    let level_options = move |selected: Level| {
+        Level::ALL
+            .into_iter()
+            .map(|level| view! { <option value=level.name() selected=level == selected>{level.to_string()}</option> })
+            .collect_view()
+    };
+    let start = move |mode: GameMode, quizzes: Vec<Quiz>, choice_seed: Option<u64>| {
+        let mut fresh = QuizState::new();
+        if let Some(seed) = choice_seed {
+            fresh = fresh.with_shuffled_choices(seed);
+        }
+        if partial_credit.get_untracked() {
+            fresh = fresh.with_credit(Credit::Partial);
+        }
+        // Both pick questions from earlier answers, so they need feedback.
+        if exam_mode.get_untracked() && !matches!(mode, GameMode::DailyReview | GameMode::Adaptive) {
+            fresh = fresh.as_exam();
+        }
+        let limit = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+        fresh = fresh.with_time_limits(limit(question_limit.get_untracked()), limit(total_limit.get_untracked()));
+        fresh.start_question(&now_millis);
+        // Sessions replay graded answers, so exam drafts are not saved. A
+        // review is saved answer by answer in the schedule instead, and an
+        // adaptive quiz only knows its questions as it goes.
+        let quiz_seed = if mode == GameMode::Random5 { seed.get_untracked() } else { None };
+        let saved = !fresh.exam && !matches!(mode, GameMode::DailyReview | GameMode::Adaptive);
+        session.set(saved.then(|| Session::new(&quizzes, &fresh, quiz_seed, now_millis())));
+        state.set(fresh);
+        set_quiz_list.set(quizzes);
+        set_game_mode.set(mode);
+    };
+    let start_adaptive = move || {
+        let pool = filters().run(&all_quizzes.get_value());
+        let mut driver = Adaptive::new(pool, min_level.get_untracked()).seed(rand::random());
+        let first = driver.first();
+        adaptive.set(Some(driver));
+        start(GameMode::Adaptive, first, shuffle_choices.get_untracked().then(rand::random));
+    };
+    let retry_mistakes = move || {
+        let (missed, mut fresh) = quiz_list.with_untracked(|quizzes| state.with_untracked(|s| s.retry_mistakes(quizzes)));
+        fresh.start_question(&now_millis);
+        adaptive.set(None);
+        session.set((!fresh.exam).then(|| Session::new(&missed, &fresh, None, now_millis()).as_retry()));
+        state.set(fresh);
+        set_quiz_list.set(missed);
+        set_game_mode.set(GameMode::Retry);
+    };
+    let back_to_menu = move || {
+        state.set(QuizState::new());
+        adaptive.set(None);
+        session.set(None);
+        set_quiz_list.set(Vec::new());
+        set_game_mode.set(GameMode::NotSelected);
+    };
+    // Keeps the saved session, or the review schedule, up to date with a
+    // graded answer, and lets an adaptive quiz pick its next question.
+    let remember = move |attempt: Attempt, at: u64| {
+        adaptive.update(|driver| {
+            if let Some(driver) = driver {
+                set_quiz_list.update(|quizzes| {
+                    driver.after(attempt.result, quizzes);
+                });
+            }
+        });
+        if game_mode.get_untracked() == GameMode::DailyReview {
+            schedule.update(|s| s.record(attempt.quiz_id, attempt.result, day(at)));
+        }
+        session.update(|saved| {
+            if let Some(saved) = saved {
+                saved.record(attempt.quiz_id, attempt.response, at);
+            }
+        });
+    };
+    // Drives the countdown and applies time limits as they run out.
+    set_interval(
+        move || {
+            let at = now_millis();
+            set_now.set(at);
+            if state.with_untracked(|s| s.time_left(&|| at) != Some(Duration::ZERO)) {
+                return;
+            }
+            let mut timeout = None;
+            quiz_list.with_untracked(|quizzes| state.update(|s| timeout = s.check_time(quizzes, &|| at)));
+            match timeout {
+                Some(Timeout::Question) => {
+                    if let Some(attempt) = state.with_untracked(|s| s.history.last().cloned()) {
+                        remember(attempt, at);
+                    }
+                }
+                Some(Timeout::Total) => session.set(None),
+                None => {}
+            }
+        },
+        Duration::from_millis(250),
+    );
 
     view! {
         <div class="container py-1">
@@ -39,35 +320,191 @@ fn App() -> impl IntoView {
                                         <img src="images/rust-quiz-full-logo.png" alt="Rust Quiz Logo" style="width:200px;margin: 20px auto;" />
                                         <h2 class="text-center mb-4">"Choose Your Quiz Mode"</h2>
                                         <div class="d-grid gap-3">
+                                            <button
+                                                class="btn btn-success btn-lg"
+                                                disabled=move || due_today().is_empty()
+                                                title="Questions you answered before come back when they are due, missed ones sooner"
+                                                on:click=move |_| start(GameMode::DailyReview, due_today(), None)
+                                            >
+                                                <i class="bi bi-calendar-check"></i> " Daily Review (" {move || due_today().len()} " due)"
+                                            </button>
+                                            <div class="row g-2">
+                                                <div class="col-12">
+                                                    <select
+                                                        class="form-select"
+                                                        aria-label="Topic"
+                                                        on:change=move |ev| set_filter_tag.set(event_target_value(&ev))
+                                                    >
+                                                        <option value="" selected=move || filter_tag.get().is_empty()>"Any topic"</option>
+                                                        {tags.get_value().into_iter().map(|tag| {
+                                                            let selected = tag == filter_tag.get_untracked();
+                                                            view! { <option value=tag.clone() selected=selected>{tag.clone()}</option> }
+                                                        }).collect_view()}
+                                                    </select>
+                                                </div>
+                                                <div class="col-6">
+                                                    <div class="input-group">
+                                                        <span class="input-group-text">"From"</span>
+                                                        <select
+                                                            class="form-select"
+                                                            on:change=move |ev| {
+                                                                if let Ok(level) = event_target_value(&ev).parse() {
+                                                                    set_min_level.set(level);
+                                                                }
+                                                            }
+                                                        >
+                                                            {level_options(min_level.get_untracked())}
+                                                        </select>
+                                                    </div>
+                                                </div>
+                                                <div class="col-6">
+                                                    <div class="input-group">
+                                                        <span class="input-group-text">"To"</span>
+                                                        <select
+                                                            class="form-select"
+                                                            on:change=move |ev| {
+                                                                if let Ok(level) = event_target_value(&ev).parse() {
+                                                                    set_max_level.set(level);
+                                                                }
+                                                            }
+                                                        >
+                                                            {level_options(max_level.get_untracked())}
+                                                        </select>
+                                                    </div>
+                                                </div>
+                                            </div>
+                                            <div class="input-group">
+                                                <span class="input-group-text"><i class="bi bi-calculator"></i></span>
+                                                <select
+                                                    class="form-select"
+                                                    aria-label="Scoring"
+                                                    on:change=move |ev| set_scoring.set(event_target_value(&ev).parse().unwrap_or_default())
+                                                >
+                                                    {Scoring::ALL
+                                                        .into_iter()
+                                                        .map(|option| view! {
+                                                            <option value=option.name() selected=option == scoring.get_untracked()>
+                                                                {format!("{} scoring", option)}
+                                                            </option>
+                                                        })
+                                                        .collect_view()}
+                                                </select>
+                                            </div>
                                             <button 
                                                 class="btn btn-outline-primary btn-lg"
+                                                disabled=move || matching_count() == 0
                                                 on:click=move |_| {
-                                                    let mut rng = thread_rng();
-                                                    let mut selected: Vec<_> = all_quizzes.get_value().clone();
-                                                    selected.shuffle(&mut rng);
-                                                    selected.truncate(5);
-                                                    set_quiz_list.set(selected);
-                                                    set_game_mode.set(GameMode::Random5);
-                                                    set_current_index.set(0);
-                                                    set_selected_answer.set(None);
-                                                    set_feedback.set(None);
-                                                    set_score.set(0);
+                                                    let chosen = seed_input.get().trim().parse::<u64>().ok();
+                                                    let new_seed = chosen.unwrap_or_else(rand::random);
+                                                    set_seed.set(Some(new_seed));
+                                                    start(
+                                                        GameMode::Random5,
+                                                        filters().count(5).seed(new_seed).run(&all_quizzes.get_value()),
+                                                        shuffle_choices.get().then_some(new_seed),
+                                                    );
                                                 }
                                             >
                                                 <i class="bi bi-dice-5"></i> " Random 5 Questions"
                                             </button>
+                                            <div class="input-group">
+                                                <span class="input-group-text"><i class="bi bi-hash"></i> " Seed"</span>
+                                                <input
+                                                    class="form-control"
+                                                    type="text"
+                                                    inputmode="numeric"
+                                                    placeholder="Optional: replay a random quiz"
+                                                    prop:value=move || seed_input.get()
+                                                    on:input=move |ev| set_seed_input.set(event_target_value(&ev))
+                                                />
+                                            </div>
+                                            <div class="form-check form-switch">
+                                                <input
+                                                    class="form-check-input"
+                                                    type="checkbox"
+                                                    id="shuffle-choices"
+                                                    prop:checked=move || shuffle_choices.get()
+                                                    on:change=move |ev| set_shuffle_choices.set(event_target_checked(&ev))
+                                                />
+                                                <label class="form-check-label" for="shuffle-choices">"Shuffle answer choices"</label>
+                                            </div>
+                                            <div class="form-check form-switch">
+                                                <input
+                                                    class="form-check-input"
+                                                    type="checkbox"
+                                                    id="partial-credit"
+                                                    prop:checked=move || partial_credit.get()
+                                                    on:change=move |ev| set_partial_credit.set(event_target_checked(&ev))
+                                                />
+                                                <label class="form-check-label" for="partial-credit">"Partial credit for several-answer questions"</label>
+                                            </div>
+                                            <div class="form-check form-switch">
+                                                <input
+                                                    class="form-check-input"
+                                                    type="checkbox"
+                                                    id="exam-mode"
+                                                    prop:checked=move || exam_mode.get()
+                                                    on:change=move |ev| set_exam_mode.set(event_target_checked(&ev))
+                                                />
+                                                <label class="form-check-label" for="exam-mode">"Exam mode: skip, flag and change answers, graded at the end"</label>
+                                            </div>
+                                            <div class="row g-2">
+                                                <div class="col-6">
+                                                    <div class="input-group">
+                                                        <span class="input-group-text"><i class="bi bi-stopwatch"></i></span>
+                                                        <select
+                                                            class="form-select"
+                                                            aria-label="Time per question"
+                                                            on:change=move |ev| set_question_limit.set(event_target_value(&ev).parse().unwrap_or(0))
+                                                        >
+                                                            {[(0, "No limit per question"), (15, "15 s per question"), (30, "30 s per question"), (60, "60 s per question")]
+                                                                .into_iter()
+                                                                .map(|(secs, label)| view! { <option value=secs selected=secs == question_limit.get_untracked()>{label}</option> })
+                                                                .collect_view()}
+                                                        </select>
+                                                    </div>
+                                                </div>
+                                                <div class="col-6">
+                                                    <div class="input-group">
+                                                        <span class="input-group-text"><i class="bi bi-hourglass-split"></i></span>
+                                                        <select
+                                                            class="form-select"
+                                                            aria-label="Total time"
+                                                            on:change=move |ev| set_total_limit.set(event_target_value(&ev).parse().unwrap_or(0))
+                                                        >
+                                                            {[(0, "No total limit"), (120, "2 min in total"), (300, "5 min in total"), (600, "10 min in total")]
+                                                                .into_iter()
+                                                                .map(|(secs, label)| view! { <option value=secs selected=secs == total_limit.get_untracked()>{label}</option> })
+                                                                .collect_view()}
+                                                        </select>
+                                                    </div>
+                                                </div>
+                                            </div>
                                             <button 
                                                 class="btn btn-outline-primary btn-lg"
+                                                disabled=move || matching_count() == 0
                                                 on:click=move |_| {
-                                                    set_quiz_list.set(all_quizzes.get_value().clone());
-                                                    set_game_mode.set(GameMode::AllQuestions);
-                                                    set_current_index.set(0);
-                                                    set_selected_answer.set(None);
-                                                    set_feedback.set(None);
-                                                    set_score.set(0);
+                                                    start(
+                                                        GameMode::AllQuestions,
+                                                        filters().run(&all_quizzes.get_value()),
+                                                        shuffle_choices.get().then(rand::random),
+                                                    );
                                                 }
                                             >
-                                                <i class="bi bi-journal-text"></i> " All " {all_quizzes.get_value().len()} " Questions"
+                                                <i class="bi bi-journal-text"></i> " All " {matching_count} " Questions"
+                                            </button>
+                                            <button
+                                                class="btn btn-outline-primary btn-lg"
+                                                disabled=move || matching_count() == 0
+                                                title="Starts at the From level and moves up or down as you answer"
+                                                on:click=move |_| start_adaptive()
+                                            >
+                                                <i class="bi bi-graph-up-arrow"></i> " Adaptive"
+                                            </button>
+                                            <button
+                                                class="btn btn-outline-secondary btn-lg"
+                                                on:click=move |_| set_game_mode.set(GameMode::Statistics)
+                                            >
+                                                <i class="bi bi-bar-chart-line"></i> " Statistics"
                                             </button>
                                         </div>
                                         <div class="text-center mt-4">
@@ -99,19 +536,240 @@ fn App() -> impl IntoView {
                             </div>
                         }.into_any()
                     }
+                    GameMode::Statistics => {
+                        let stats = history.with(|h| Stats::from_records(&all_quizzes.get_value(), &h.records));
+                        let overall = stats.overall;
+                        let tags: Vec<(String, Tally)> =
+                            stats.weakest_tags().into_iter().map(|(tag, tally)| (tag.to_string(), tally)).collect();
+                        let levels: Vec<(Level, Tally)> = stats.by_level.into_iter().collect();
+                        view! {
+                            <div class="card p-4">
+                                <div class="d-flex justify-content-between align-items-center mb-3">
+                                    <h2 class="h4 mb-0"><i class="bi bi-bar-chart-line"></i> " Statistics"</h2>
+                                    <button
+                                        class="btn btn-outline-secondary btn-sm"
+                                        on:click=move |_| back_to_menu()
+                                    >
+                                        <i class="bi bi-arrow-left"></i> " Back to Menu"
+                                    </button>
+                                </div>
+                                {if overall.answered == 0 {
+                                    view! {
+                                        <div class="alert alert-info">"No answers recorded yet. Finish a quiz to see your statistics."</div>
+                                    }.into_any()
+                                } else {
+                                    view! {
+                                        <p class="text-muted">
+                                            {format!(
+                                                "{} answers, {} correct ({:.0}%). Topics you miss most come first.",
+                                                overall.answered,
+                                                overall.correct,
+                                                overall.accuracy()
+                                            )}
+                                        </p>
+                                        <table class="table table-sm align-middle">
+                                            <thead>
+                                                <tr><th>"Topic"</th><th class="text-end">"Answered"</th><th class="text-end">"Correct"</th><th>"Accuracy"</th></tr>
+                                            </thead>
+                                            <tbody>
+                                                {tags.into_iter().map(|(tag, tally)| tally_row(tag, tally)).collect_view()}
+                                            </tbody>
+                                        </table>
+                                        <table class="table table-sm align-middle">
+                                            <thead>
+                                                <tr><th>"Level"</th><th class="text-end">"Answered"</th><th class="text-end">"Correct"</th><th>"Accuracy"</th></tr>
+                                            </thead>
+                                            <tbody>
+                                                {levels.into_iter().map(|(level, tally)| tally_row(level.to_string(), tally)).collect_view()}
+                                            </tbody>
+                                        </table>
+                                    }.into_any()
+                                }}
+                            </div>
+                        }.into_any()
+                    }
+                    _ if finished.get() => {
+                        let total = quiz_list.with(Vec::len);
+                        view! {
+                            <div class="card p-4">
+                                <div class="alert alert-info text-center">
+                                    <h3 class="alert-heading">
+                                        <i class="bi bi-trophy-fill"></i> " Quiz Complete!"
+                                    </h3>
+                                    <p class="fs-2 mb-0">
+                                        "Final Score: " {move || state.with(|s| s.score)} "/" {total}
+                                    </p>
+                                    <p class="mb-0">
+                                        {move || {
+                                            let scoring = scoring.get();
+                                            let report = quiz_list.with(|quizzes| {
+                                                state.with(|s| s.report(quizzes, scoring.strategy().as_ref()))
+                                            });
+                                            format!(
+                                                "{:.1} of {:.1} points ({:.0}%), {} scoring",
+                                                report.points,
+                                                report.max_points,
+                                                report.percent(),
+                                                scoring
+                                            )
+                                        }}
+                                    </p>
+                                </div>
+                                {(game_mode.get_untracked() == GameMode::DailyReview).then(|| {
+                                    let today = day(now_millis());
+                                    schedule.with_untracked(Schedule::next_due).map(|due| view! {
+                                        <p class="text-center text-muted">
+                                            <i class="bi bi-calendar-event"></i>
+                                            {format!(" Next review in {} day(s)", due.saturating_sub(today))}
+                                        </p>
+                                    })
+                                })}
+                                {adaptive.with_untracked(|driver| driver.as_ref().map(|driver| view! {
+                                    <p class="text-center text-muted">
+                                        <i class="bi bi-graph-up-arrow"></i>
+                                        {format!(" Level reached: {} (highest: {})", driver.level(), driver.highest)}
+                                    </p>
+                                }))}
+                                <h4 class="mb-3">"Review"</h4>
+                                <div class="list-group mb-4">
+                                    {move || {
+                                        let quizzes = quiz_list.get();
+                                        state.with(|s| s.history.clone()).into_iter().filter_map(|attempt| {
+                                            let quiz = quizzes.iter().find(|q| q.id == attempt.quiz_id)?;
+                                            let (style, icon) = match attempt.result {
+                                                AnswerResult::Correct => ("success", "check-circle-fill"),
+                                                AnswerResult::Partial => ("warning", "dash-circle-fill"),
+                                                _ => ("danger", "x-circle-fill"),
+                                            };
+                                            let correct = attempt.result == AnswerResult::Correct;
+                                            let your_answer = if attempt.timed_out {
+                                                "(ran out of time)".to_string()
+                                            } else {
+                                                describe_response(quiz, &attempt.response)
+                                            };
+                                            let solution = describe_solution(quiz);
+                                            let seconds = attempt.time_taken.map(|ms| format!("{:.1}s", ms as f64 / 1000.0));
+                                            Some(view! {
+                                                <div class="list-group-item">
+                                                    <div class="d-flex justify-content-between align-items-start">
+                                                        <h5 class="mb-2">
+                                                            <i class=format!("bi bi-{} text-{} me-2", icon, style)></i>
+                                                            {quiz.title.to_string()}
+                                                        </h5>
+                                                        {seconds.map(|s| view! { <small class="text-muted"><i class="bi bi-stopwatch"></i> " " {s}</small> })}
+                                                    </div>
+                                                    <p class="mb-2">{quiz.question.to_string()}</p>
+                                                    <div class="mb-1">"Your answer:"</div>
+                                                    <pre class="review-answer">{your_answer}</pre>
+                                                    {(!correct).then(|| view! {
+                                                        <div class="mb-1">"Correct answer:"</div>
+                                                        <pre class="review-answer">{solution}</pre>
+                                                    })}
+                                                    <p class="mb-0 text-muted">{quiz.explanation.to_string()}</p>
+                                                </div>
+                                            })
+                                        }).collect_view()
+                                    }}
+                                </div>
+                                <div class="d-flex gap-2 justify-content-center">
+                                    <button
+                                        class="btn btn-primary"
+                                        on:click=move |_| back_to_menu()
+                                    >
+                                        <i class="bi bi-house-fill"></i> " Back to Menu"
+                                    </button>
+                                    {move || {
+                                        let missed = quiz_list.with(|quizzes| state.with(|s| s.mistakes(quizzes).len()));
+                                        (missed > 0).then(|| view! {
+                                            <button
+                                                class="btn btn-warning"
+                                                on:click=move |_| retry_mistakes()
+                                            >
+                                                <i class="bi bi-arrow-counterclockwise"></i> {format!(" Retry {} Mistake{}", missed, if missed == 1 { "" } else { "s" })}
+                                            </button>
+                                        })
+                                    }}
+                                    {(!matches!(game_mode.get_untracked(), GameMode::DailyReview | GameMode::Retry)).then(|| view! {
+                                        <button
+                                            class="btn btn-outline-primary"
+                                            on:click=move |_| {
+                                                let mode = game_mode.get();
+                                                if mode == GameMode::Adaptive {
+                                                    start_adaptive();
+                                                    return;
+                                                }
+                                                let new_seed = rand::random();
+                                                let quizzes = if mode == GameMode::Random5 {
+                                                    set_seed.set(Some(new_seed));
+                                                    filters().count(5).seed(new_seed).run(&all_quizzes.get_value())
+                                                } else {
+                                                    filters().run(&all_quizzes.get_value())
+                                                };
+                                                start(mode, quizzes, shuffle_choices.get().then_some(new_seed));
+                                            }
+                                        >
+                                            <i class="bi bi-arrow-repeat"></i> " Play Again"
+                                        </button>
+                                    })}
+                                </div>
+                            </div>
+                        }.into_any()
+                    }
                     _ => {
-                        let quizzes = quiz_list.get();
+                        let played = quiz_list.with(Vec::len);
+                        let total = adaptive.with_untracked(|driver| driver.as_ref().map(|d| d.total(played))).unwrap_or(played);
                         let index = current_index.get();
-                        if index < quizzes.len() {
-                            let quiz = &quizzes[index];
-                            let total = quizzes.len();
+                        if let Some(quiz) = quiz_list.with(|quizzes| quizzes.get(index).cloned()) {
                             let current = index + 1;
-                            let quiz_question = quiz.question;
-                            let quiz_code = quiz.code;
-                            let quiz_choices = quiz.choices.clone();
-                            let quiz_correct = quiz.correct;
-                            let quiz_explanation = quiz.explanation;
-                            
+                            // Shown in session order, keeping authored indices for answers.
+                            let presented: Vec<(usize, String)> = state
+                                .with_untracked(|s| s.presented_choices(&quiz).into_iter().map(|(i, c)| (i, c.to_string())).collect());
+                            let multiple = quiz.correct.is_multiple();
+                            let typed = matches!(quiz.correct, Answer::Text(_));
+                            let ordering = matches!(quiz.correct, Answer::Order { .. });
+                            let blanks = matches!(quiz.correct, Answer::Blanks { .. });
+                            let quiz_question = quiz.question.clone();
+                            let quiz_code = quiz.code.clone();
+                            let quiz_explanation = quiz.explanation.clone();
+                            let lines = quiz.choices.clone();
+                            let quiz = StoredValue::new(quiz);
+                            let answering = move || phase.get() == Phase::Answering;
+                            let exam = state.with_untracked(|s| s.exam);
+                            let navigate = move |to: usize| quiz_list.with_untracked(|quizzes| state.update(|s| s.go_to(quizzes, to)));
+                            let submit_exam = move || {
+                                let (unanswered, flagged) = quiz_list.with_untracked(|quizzes| {
+                                    state.with_untracked(|s| (s.unanswered(quizzes).len(), s.flagged.len()))
+                                });
+                                let ready = (unanswered == 0 && flagged == 0)
+                                    || window()
+                                        .confirm_with_message(&format!(
+                                            "{} unanswered and {} flagged question(s). Submit the exam anyway?",
+                                            unanswered, flagged
+                                        ))
+                                        .unwrap_or(false);
+                                if ready {
+                                    quiz_list.with_untracked(|quizzes| state.update(|s| s.submit_exam(quizzes)));
+                                }
+                            };
+                            let submit = move || {
+                                quiz_list.with_untracked(|quizzes| state.update(|s| {
+                                    s.submit_selection_at(quizzes, &now_millis);
+                                }));
+                                if state.with_untracked(|s| s.finished) {
+                                    // The whole quiz ran out of time.
+                                    session.set(None);
+                                    return;
+                                }
+                                let graded = state.with_untracked(|s| s.feedback.is_some().then(|| s.history.last().cloned()));
+                                if let Some(Some(attempt)) = graded {
+                                    remember(attempt, now_millis());
+                                }
+                            };
+                            let next = move || quiz_list.with_untracked(|quizzes| state.update(|s| {
+                                s.next(quizzes);
+                                s.start_question(&now_millis);
+                            }));
+
                             view! {
                                 <div class="card p-4">
                                     <div class="d-flex justify-content-between align-items-center mb-3">
@@ -119,174 +777,262 @@ fn App() -> impl IntoView {
                                             <span class="badge bg-secondary me-2">
                                                 "Question " {current} " of " {total}
                                             </span>
+                                            {move || state.with(|s| s.time_left(&|| now.get())).map(|left| {
+                                                let secs = left.as_secs_f64().ceil() as u64;
+                                                view! {
+                                                    <span class=format!("badge me-2 {}", if secs <= 10 { "bg-danger" } else { "bg-dark" }) title="Time left">
+                                                        <i class="bi bi-stopwatch"></i> " " {format!("{}:{:02}", secs / 60, secs % 60)}
+                                                    </span>
+                                                }
+                                            })}
                                             <span class="badge bg-primary">
-                                                "Score: " {score.get()}
+                                                "Score: " {move || state.with(|s| s.score)}
                                             </span>
+                                            {move || adaptive.with(|driver| driver.as_ref().map(Adaptive::level)).map(|level| view! {
+                                                <span class="badge bg-info text-dark ms-2" title="Moves up after 3 correct answers in a row, down after 2 misses">
+                                                    <i class="bi bi-graph-up-arrow"></i> " " {level.to_string()}
+                                                </span>
+                                            })}
+                                            {move || (game_mode.get() == GameMode::Random5).then(|| seed.get()).flatten().map(|seed| view! {
+                                                <span class="badge bg-light text-dark ms-2" title="Enter this seed on the menu to replay the same questions">
+                                                    <i class="bi bi-hash"></i> "Seed " {seed}
+                                                </span>
+                                            })}
                                         </div>
-                                        <button 
+                                        <button
                                             class="btn btn-outline-secondary btn-sm"
-                                            on:click=move |_| {
-                                                set_game_mode.set(GameMode::NotSelected);
-                                                set_quiz_list.set(Vec::new());
-                                                set_current_index.set(0);
-                                                set_selected_answer.set(None);
-                                                set_feedback.set(None);
-                                                set_score.set(0);
-                                            }
+                                            on:click=move |_| back_to_menu()
                                         >
                                             <i class="bi bi-arrow-left"></i> " Back to Menu"
                                         </button>
                                     </div>
-                                    
+
+                                    {exam.then(|| view! {
+                                        <div class="d-flex flex-wrap gap-1 mb-3" aria-label="Questions">
+                                            {(0..total).map(|i| view! {
+                                                <button
+                                                    class=move || {
+                                                        let (answered, flagged) = state.with(|s| (s.is_answered(i), s.is_flagged(i)));
+                                                        let style = if i == index {
+                                                            "btn-primary"
+                                                        } else if answered {
+                                                            "btn-secondary"
+                                                        } else {
+                                                            "btn-outline-secondary"
+                                                        };
+                                                        format!("btn btn-sm palette-item {}{}", style, if flagged { " flagged" } else { "" })
+                                                    }
+                                                    title=move || if state.with(|s| s.is_flagged(i)) { "Flagged for review" } else { "" }
+                                                    on:click=move |_| navigate(i)
+                                                >
+                                                    {i + 1}
+                                                </button>
+                                            }).collect_view()}
+                                        </div>
+                                    })}
+
                                     <h2 class="h4 mb-3">{quiz_question}</h2>
-                                    
-                                    {move || {
-                                        if let Some(code) = quiz_code {
-                                            view! {
-                                                <pre class="mb-3"><code>{code}</code></pre>
-                                            }.into_any()
-                                        } else {
-                                            view! { <div></div> }.into_any()
-                                        }
+
+                                    {if let Some(code) = quiz_code.clone().filter(|_| blanks) {
+                                        code_view(&code, move |i| view! {
+                                            <input
+                                                class="blank-input"
+                                                type="text"
+                                                size="6"
+                                                disabled=move || !answering()
+                                                prop:value=move || state.with(|s| match &s.selection {
+                                                    Some(Response::Blanks(filled)) => filled.get(i).cloned().unwrap_or_default(),
+                                                    _ => String::new(),
+                                                })
+                                                on:input=move |ev| {
+                                                    let value = event_target_value(&ev);
+                                                    state.update(|s| quiz.with_value(|q| s.fill_blank(q, i, value)));
+                                                }
+                                            />
+                                        }.into_any())
+                                    } else if let Some(code) = quiz_code {
+                                        code_view(&code, |_| view! { {BLANK} }.into_any())
+                                    } else {
+                                        view! { <div></div> }.into_any()
                                     }}
-                                    
-                                    <div class="mb-3">
-                                        {quiz_choices.iter().enumerate().map(|(i, choice)| {
-                                            let choice_text = choice.to_string();
-                                            view! {
-                                                <div class="form-check rounded ps-0">
-                                                    <label class="form-check mb-2 w-100 border rounded p-3 d-flex gap-2 align-items-start">
-                                                        <input
-                                                            class="form-check-input mt-1"
-                                                            type="radio"
-                                                            name="answer"
-                                                            value={i}
-                                                            disabled=move || feedback.get().is_some()
-                                                            prop:checked=move || selected_answer.get() == Some(i)
-                                                            on:change=move |_| set_selected_answer.set(Some(i))
-                                                        />
-                                                        <span>{choice_text}</span>
-                                                    </label>
+
+                                    {multiple.then(|| view! {
+                                        <p class="text-muted mb-2">
+                                            <i class="bi bi-ui-checks"></i> " Select all that apply"
+                                        </p>
+                                    })}
+
+                                    {if typed {
+                                        view! {
+                                            <div class="mb-3">
+                                                <input
+                                                    class="form-control form-control-lg font-monospace"
+                                                    type="text"
+                                                    placeholder="Type your answer"
+                                                    disabled=move || !answering()
+                                                    prop:value=move || state.with(|s| match &s.selection {
+                                                        Some(Response::Text(text)) => text.clone(),
+                                                        _ => String::new(),
+                                                    })
+                                                    on:input=move |ev| state.update(|s| s.select(Response::Text(event_target_value(&ev))))
+                                                />
+                                            </div>
+                                        }.into_any()
+                                    } else if ordering {
+                                        view! {
+                                            <div class="mb-3">
+                                                <p class="text-muted mb-2">
+                                                    <i class="bi bi-sort-down"></i> " Click the lines in the order they should appear"
+                                                </p>
+                                                <div class="d-grid gap-2 mb-3">
+                                                    {presented.into_iter().map(|(i, line_text)| {
+                                                        view! {
+                                                            <button
+                                                                class="btn btn-outline-secondary text-start code-line"
+                                                                disabled=move || !answering() || state.with(|s| s.is_selected(i))
+                                                                on:click=move |_| state.update(|s| quiz.with_value(|q| s.select_choice(q, i)))
+                                                            >
+                                                                <code>{line_text}</code>
+                                                            </button>
+                                                        }
+                                                    }).collect_view()}
                                                 </div>
-                                            }
-                                        }).collect_view()}
-                                    </div>
-                                    
+                                                <pre class="mb-0"><code>
+                                                    {move || state.with(|s| match &s.selection {
+                                                        Some(Response::Order(order)) => order
+                                                            .iter()
+                                                            .map(|&i| lines[i].to_string())
+                                                            .collect::<Vec<_>>()
+                                                            .join("\n"),
+                                                        _ => String::new(),
+                                                    })}
+                                                </code></pre>
+                                            </div>
+                                        }.into_any()
+                                    } else if blanks {
+                                        view! { <div></div> }.into_any()
+                                    } else {
+                                        view! {
+                                            <div class="mb-3">
+                                                {presented.into_iter().map(|(i, choice_text)| {
+                                                    view! {
+                                                        <div class="form-check rounded ps-0">
+                                                            <label class="form-check mb-2 w-100 border rounded p-3 d-flex gap-2 align-items-start">
+                                                                <input
+                                                                    class="form-check-input mt-1"
+                                                                    type=if multiple { "checkbox" } else { "radio" }
+                                                                    name="answer"
+                                                                    value={i}
+                                                                    disabled=move || !answering()
+                                                                    prop:checked=move || state.with(|s| s.is_selected(i))
+                                                                    on:change=move |_| state.update(|s| quiz.with_value(|q| s.select_choice(q, i)))
+                                                                />
+                                                                <span>{choice_text}</span>
+                                                            </label>
+                                                        </div>
+                                                    }
+                                                }).collect_view()}
+                                            </div>
+                                        }.into_any()
+                                    }}
+
                                     {move || {
-                                        if let Some((is_correct, explanation)) = feedback.get() {
+                                        let explanation = quiz_explanation.clone();
+                                        state.with(|s| s.feedback.as_ref().map(|f| (f.result, f.timed_out))).map(|(result, timed_out)| {
+                                            let (style, icon, heading) = match result {
+                                                _ if timed_out => ("danger", "alarm-fill", "Time's up!"),
+                                                AnswerResult::Correct => ("success", "check-circle-fill", "Correct!"),
+                                                AnswerResult::Partial => ("warning", "dash-circle-fill", "Partially correct"),
+                                                _ => ("danger", "x-circle-fill", "Incorrect"),
+                                            };
                                             view! {
-                                                <div class=format!("alert alert-{} d-flex align-items-start", 
-                                                    if is_correct { "success" } else { "danger" })
-                                                >
-                                                    <i class=format!("bi bi-{} me-2 fs-4", 
-                                                        if is_correct { "check-circle-fill" } else { "x-circle-fill" })
-                                                    ></i>
+                                                <div class=format!("alert alert-{} d-flex align-items-start", style)>
+                                                    <i class=format!("bi bi-{} me-2 fs-4", icon)></i>
                                                     <div>
-                                                        <h4 class="alert-heading">
-                                                            {if is_correct { "Correct!" } else { "Incorrect" }}
-                                                        </h4>
+                                                        <h4 class="alert-heading">{heading}</h4>
                                                         <p class="mb-0">{explanation}</p>
                                                     </div>
                                                 </div>
-                                            }.into_any()
-                                        } else {
-                                            view! { <div></div> }.into_any()
-                                        }
+                                            }
+                                        })
                                     }}
-                                    
+
                                     <div class="d-flex gap-2 mt-3">
                                         {move || {
-                                            let feedback_value = feedback.get();
-                                            if feedback_value.is_none() {
+                                            if exam {
+                                                view! {
+                                                    <>
+                                                        <button
+                                                            class="btn btn-outline-secondary"
+                                                            disabled=index == 0
+                                                            on:click=move |_| navigate(index - 1)
+                                                        >
+                                                            <i class="bi bi-arrow-left"></i> " Back"
+                                                        </button>
+                                                        <button
+                                                            class="btn btn-outline-secondary"
+                                                            on:click=move |_| navigate((index + 1) % total)
+                                                        >
+                                                            "Next " <i class="bi bi-arrow-right"></i>
+                                                        </button>
+                                                        <button
+                                                            class="btn btn-outline-warning"
+                                                            on:click=move |_| state.update(QuizState::toggle_flag)
+                                                        >
+                                                            <i class="bi bi-flag-fill"></i>
+                                                            {move || if state.with(|s| s.is_flagged(index)) { " Unflag" } else { " Flag" }}
+                                                        </button>
+                                                        <button
+                                                            class="btn btn-outline-secondary"
+                                                            disabled=move || state.with(|s| s.selection.is_none())
+                                                            on:click=move |_| state.update(QuizState::clear_selection)
+                                                        >
+                                                            <i class="bi bi-arrow-counterclockwise"></i> " Clear"
+                                                        </button>
+                                                        <button
+                                                            class="btn btn-primary ms-auto"
+                                                            on:click=move |_| submit_exam()
+                                                        >
+                                                            <i class="bi bi-send-check"></i> " Submit Exam"
+                                                        </button>
+                                                    </>
+                                                }.into_any()
+                                            } else if answering() {
                                                 view! {
                                                     <>
-                                                        <button 
+                                                        <button
                                                             class="btn btn-primary"
-                                                            disabled=move || selected_answer.get().is_none()
-                                                            on:click=move |_| {
-                                                                if let Some(answer_idx) = selected_answer.get() {
-                                                                    let correct = quiz_correct == answer_idx;
-                                                                    if correct {
-                                                                        set_score.update(|s| *s += 1);
-                                                                    }
-                                                                    set_feedback.set(Some((correct, quiz_explanation.to_string())));
-                                                                }
-                                                            }
+                                                            disabled=move || !state.with(|s| quiz.with_value(|q| s.can_submit(q)))
+                                                            on:click=move |_| submit()
                                                         >
                                                             <i class="bi bi-check-lg"></i> " Submit Answer"
                                                         </button>
-                                                        <button 
+                                                        <button
                                                             class="btn btn-outline-secondary"
-                                                            disabled=move || selected_answer.get().is_none()
-                                                            on:click=move |_| {
-                                                                set_selected_answer.set(None);
-                                                            }
+                                                            disabled=move || state.with(|s| s.selection.is_none())
+                                                            on:click=move |_| state.update(QuizState::clear_selection)
                                                         >
                                                             <i class="bi bi-arrow-counterclockwise"></i> " Clear"
                                                         </button>
                                                     </>
                                                 }.into_any()
-                                            } else if current_index.get() < total - 1 {
+                                            } else if current < total {
                                                 view! {
-                                                    <button 
+                                                    <button
                                                         class="btn btn-primary"
-                                                        on:click=move |_| {
-                                                            set_current_index.update(|i| *i += 1);
-                                                            set_selected_answer.set(None);
-                                                            set_feedback.set(None);
-                                                        }
+                                                        on:click=move |_| next()
                                                     >
                                                         "Next Question " <i class="bi bi-arrow-right"></i>
                                                     </button>
                                                 }.into_any()
                                             } else {
                                                 view! {
-                                                    <div class="w-100">
-                                                        <div class="alert alert-info text-center">
-                                                            <h3 class="alert-heading">
-                                                                <i class="bi bi-trophy-fill"></i> " Quiz Complete!"
-                                                            </h3>
-                                                            <p class="fs-2 mb-0">
-                                                                "Final Score: " {score.get()} "/" {total}
-                                                            </p>
-                                                        </div>
-                                                        <div class="d-flex gap-2 justify-content-center">
-                                                            <button 
-                                                                class="btn btn-primary"
-                                                                on:click=move |_| {
-                                                                    set_game_mode.set(GameMode::NotSelected);
-                                                                    set_quiz_list.set(Vec::new());
-                                                                    set_current_index.set(0);
-                                                                    set_selected_answer.set(None);
-                                                                    set_feedback.set(None);
-                                                                    set_score.set(0);
-                                                                }
-                                                            >
-                                                                <i class="bi bi-house-fill"></i> " Back to Menu"
-                                                            </button>
-                                                            <button 
-                                                                class="btn btn-outline-primary"
-                                                                on:click=move |_| {
-                                                                    let current_mode = game_mode.get();
-                                                                    if current_mode == GameMode::Random5 {
-                                                                        let mut rng = thread_rng();
-                                                                        let mut selected: Vec<_> = all_quizzes.get_value().clone();
-                                                                        selected.shuffle(&mut rng);
-                                                                        selected.truncate(5);
-                                                                        set_quiz_list.set(selected);
-                                                                    } else {
-                                                                        set_quiz_list.set(all_quizzes.get_value().clone());
-                                                                    }
-                                                                    set_current_index.set(0);
-                                                                    set_selected_answer.set(None);
-                                                                    set_feedback.set(None);
-                                                                    set_score.set(0);
-                                                                }
-                                                            >
-                                                                <i class="bi bi-arrow-repeat"></i> " Play Again"
-                                                            </button>
-                                                        </div>
-                                                    </div>
+                                                    <button
+                                                        class="btn btn-primary"
+                                                        on:click=move |_| next()
+                                                    >
+                                                        <i class="bi bi-trophy"></i> " See Results"
+                                                    </button>
                                                 }.into_any()
                                             }
                                         }}
@@ -306,3 +1052,22 @@ fn App() -> impl IntoView {
         </div>
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reloaded_retry_round_is_not_logged() {
+        let missed: Vec<Quiz> = quiz_bank().into_iter().take(2).collect();
+        let saved = Session::new(&missed, &QuizState::new(), None, 1_000).as_retry();
+
+        let reloaded = Session::from_json(&saved.to_json()).unwrap();
+
+        assert_eq!(resumed_mode(&reloaded), GameMode::Retry);
+        assert!(!is_logged(resumed_mode(&reloaded)));
+        let quiz = Session::new(&missed, &QuizState::new(), Some(5), 1_000);
+        assert!(is_logged(resumed_mode(&quiz)));
+    }
+}